@@ -0,0 +1,61 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use itertools::Itertools;
+
+use advent_of_code_2016::computer::reference::Interpreter;
+use advent_of_code_2016::computer::{parse_program, Computer, Register};
+
+/// Compares the original hash map based interpreter to the pre-decoded `Computer`,
+/// both without and with loop optimizations.
+///
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench computer_bench
+/// ```
+fn computers(c: &mut Criterion) {
+    let inputs = [
+        (
+            "day12",
+            include_str!("../inputs/2016/day_12.txt"),
+            (Register::C, 0),
+            0,
+        ),
+        (
+            "day23",
+            include_str!("../inputs/2016/day_23.txt"),
+            (Register::A, 7),
+            0,
+        ),
+        (
+            "day25",
+            include_str!("../inputs/2016/day_25.txt"),
+            (Register::A, 0),
+            7,
+        ),
+    ];
+
+    for (name, input, initial, output_len) in inputs {
+        let program = parse_program(&input.lines().collect_vec().join("\n")).unwrap();
+        let mut group = c.benchmark_group(name);
+        group.sample_size(10);
+
+        group.bench_function("interpreter", |b| {
+            let interpreter = Interpreter::new(program.clone(), vec![initial]);
+            b.iter(|| interpreter.run(output_len));
+        });
+
+        group.bench_function("computer", |b| {
+            let computer = Computer::from_program(program.clone(), vec![initial]);
+            b.iter(|| computer.clone().run(false, output_len));
+        });
+
+        group.bench_function("computer_optimized", |b| {
+            let computer = Computer::from_program(program.clone(), vec![initial]);
+            b.iter(|| computer.clone().run(true, output_len));
+        });
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, computers);
+criterion_main!(benches);
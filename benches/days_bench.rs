@@ -0,0 +1,128 @@
+use std::ops::Not;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use std::{fs, io};
+
+use criterion::{criterion_group, Criterion};
+use serde_json::Value as Json;
+
+use advent_of_code_2016::solutions::{self, Day};
+
+/// Slower than the baseline by more than this fraction is reported as a regression.
+const REGRESSION: f64 = 0.1;
+
+/// Benchmarks both parts of every day with an input in `./inputs`, in a group per day and year.
+///
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench days_bench
+/// $ cargo bench --bench days_bench -- y2016_day12
+/// ```
+///
+/// Afterwards it prints the median runtimes, compared with the previous run. To compare with a saved
+/// baseline instead, save one and pass its name later:
+/// ```shell
+/// $ cargo bench --bench days_bench -- --save-baseline before
+/// $ cargo bench --bench days_bench -- --baseline before
+/// ```
+fn days(c: &mut Criterion) {
+    for day in solutions::days() {
+        let input = match day.read_input() {
+            Ok(input) if input.trim().is_empty().not() => input,
+            Ok(_) => continue,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => panic!("{}", e),
+        };
+
+        let mut group = c.benchmark_group(group_name(day));
+        group.sample_size(10);
+
+        for part in [1, 2] {
+            group.bench_function(format!("part{}", part), |b| {
+                b.iter(|| day.solve(part, &input));
+            });
+        }
+
+        group.finish();
+    }
+}
+
+fn group_name(day: &Day) -> String {
+    format!("y{}_day{:0>2}", day.year, day.day)
+}
+
+/// Prints a table with the median runtime of every part that was measured, next to the baseline.
+///
+/// The medians are read from the estimates Criterion stores, with the relative change it measured
+/// against the baseline given to `--baseline`, or else against the previous run.
+fn summary() {
+    let args = std::env::args().collect::<Vec<_>>();
+    let baseline = args
+        .iter()
+        .position(|a| a == "--baseline")
+        .and_then(|idx| args.get(idx + 1))
+        .map_or("previous", |name| name.as_str());
+
+    println!(
+        "{:<14}{:<8}{:>12}{:>12}{:>10}",
+        "day", "part", "median", baseline, "change"
+    );
+    let mut regressions = 0;
+
+    for day in solutions::days() {
+        for part in [1, 2] {
+            let dir = Path::new("target/criterion")
+                .join(group_name(day))
+                .join(format!("part{}", part));
+            let Some((median, modified)) = estimate(&dir.join("new")) else {
+                continue;
+            };
+            // A change from an earlier run is stale when nothing was compared this time
+            let change = estimate(&dir.join("change"))
+                .filter(|(_, changed)| *changed >= modified)
+                .map(|(change, _)| change);
+
+            let regressed = change.is_some_and(|change| change > REGRESSION);
+            regressions += usize::from(regressed);
+
+            println!(
+                "{:<14}{:<8}{:>12}{:>12}{:>10}{}",
+                group_name(day),
+                format!("part{}", part),
+                format_nanos(median),
+                change.map_or("-".to_string(), |c| format_nanos(median / (1.0 + c))),
+                change.map_or("-".to_string(), |c| format!("{:+.1}%", c * 100.0)),
+                if regressed { "  REGRESSED" } else { "" }
+            );
+        }
+    }
+
+    if regressions > 0 {
+        println!(
+            "{} parts regressed by more than {}%",
+            regressions,
+            REGRESSION * 100.0
+        );
+    }
+}
+
+/// The median point estimate stored in the directory, with when it was written.
+fn estimate(dir: &Path) -> Option<(f64, SystemTime)> {
+    let path = dir.join("estimates.json");
+    let estimates: Json = serde_json::from_str(&fs::read_to_string(&path).ok()?).ok()?;
+    let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+
+    Some((estimates["median"]["point_estimate"].as_f64()?, modified))
+}
+
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.2?}", Duration::from_secs_f64(nanos / 1e9))
+}
+
+criterion_group!(benches, days);
+
+fn main() {
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+    summary();
+}
@@ -0,0 +1,322 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Not;
+use std::panic;
+use std::time::{Duration, Instant};
+
+use serde_json::Value as Json;
+
+use crate::solutions::{self, Day, SolveError};
+
+/// The expected answers of every day, for every input they were checked on.
+///
+/// Read from JSON mapping years and days to a list of sets, answers being strings exactly as the
+/// solutions print them:
+/// ```json
+/// {
+///   "2016": {
+///     "day_12": [
+///       { "input": "inputs/2016/day_12.txt", "part_1": "318009", "part_2": "9227663" },
+///       { "input": "inputs/alice/2016/day_12.txt", "part_1": "317993" }
+///     ]
+///   }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Manifest {
+    sets: BTreeMap<(u16, u8), Vec<AnswerSet>>,
+}
+
+/// An input, and the answers expected for it when known.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AnswerSet {
+    /// The path of the input file.
+    pub input: String,
+    pub answers: [Option<String>; 2],
+}
+
+impl Manifest {
+    pub fn from_json(json: &str) -> Result<Self, ManifestError> {
+        let json: Json =
+            serde_json::from_str(json).map_err(|e| ManifestError::Json(e.to_string()))?;
+        let years = json
+            .as_object()
+            .ok_or(ManifestError::Field("years".to_string()))?;
+
+        let mut sets = BTreeMap::new();
+        for (year_key, days) in years {
+            let days = days
+                .as_object()
+                .ok_or_else(|| ManifestError::Field(year_key.clone()))?;
+
+            for (day_key, entries) in days {
+                let key = format!("{}.{}", year_key, day_key);
+                let day = year_key
+                    .parse::<u16>()
+                    .ok()
+                    .zip(
+                        day_key
+                            .strip_prefix("day_")
+                            .and_then(|day| day.parse::<u8>().ok()),
+                    )
+                    .filter(|(year, day)| solutions::day(*year, *day).is_some())
+                    .ok_or_else(|| ManifestError::Day(key.clone()))?;
+                sets.insert(day, Self::entries(&key, entries)?);
+            }
+        }
+
+        Ok(Self { sets })
+    }
+
+    fn entries(key: &str, entries: &Json) -> Result<Vec<AnswerSet>, ManifestError> {
+        let field = |name: &str| ManifestError::Field(format!("{}.{}", key, name));
+
+        entries
+            .as_array()
+            .ok_or_else(|| field("sets"))?
+            .iter()
+            .map(|entry| {
+                let input = entry["input"].as_str().ok_or_else(|| field("input"))?;
+                let answer = |name: &str| match &entry[name] {
+                    Json::Null => Ok(None),
+                    Json::String(answer) => Ok(Some(answer.clone())),
+                    _ => Err(field(name)),
+                };
+
+                Ok(AnswerSet {
+                    input: input.to_string(),
+                    answers: [answer("part_1")?, answer("part_2")?],
+                })
+            })
+            .collect()
+    }
+
+    /// The sets of the day, or one with its default input and no answers when it has none.
+    pub fn sets(&self, day: &Day) -> Vec<AnswerSet> {
+        match self.sets.get(&(day.year, day.day)) {
+            Some(sets) if sets.is_empty().not() => sets.clone(),
+            _ => vec![AnswerSet {
+                input: day.input_path(),
+                answers: [None, None],
+            }],
+        }
+    }
+}
+
+/// The manifest could not be read.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ManifestError {
+    /// The manifest is not valid JSON.
+    Json(String),
+    /// The keys are not a year and `day_` followed by the number of a solved day.
+    Day(String),
+    /// The field is missing or has an invalid value.
+    Field(String),
+}
+
+impl Display for ManifestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ManifestError::Json(error) => write!(f, "invalid JSON: {}", error),
+            ManifestError::Day(key) => write!(f, "unknown day `{}`", key),
+            ManifestError::Field(name) => write!(f, "missing or invalid `{}`", name),
+        }
+    }
+}
+
+impl Error for ManifestError {}
+
+/// How the answer to a part compares with the expected one.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// There is no expected answer, holding the one given now.
+    Missing {
+        actual: String,
+    },
+    /// The day rejected the input, with why.
+    Rejected(String),
+    /// The solution panicked, with the panic message.
+    Panicked(String),
+}
+
+/// The verdict for one part of a day on one input, with how long solving it took.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Check {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub verdict: Verdict,
+    pub elapsed: Duration,
+}
+
+/// Solves both parts on the input, the contents of the set's input file, comparing with its answers.
+/// A part the day doesn't have is left out, unless the set has an answer for it.
+///
+/// A solution panicking (on an input it doesn't expect) is caught, the panic hook still reports it.
+pub fn verify(day: &Day, set: &AnswerSet, input: &str) -> Vec<Check> {
+    [1, 2]
+        .into_iter()
+        .filter_map(|part| {
+            let start = Instant::now();
+            let actual = panic::catch_unwind(|| day.solve(part, input));
+            let elapsed = start.elapsed();
+
+            let verdict = match (actual, &set.answers[part as usize - 1]) {
+                (Ok(Err(SolveError::NoSuchPart(_))), None) => return None,
+                (Err(panic), _) => Verdict::Panicked(panic_message(panic)),
+                (Ok(Err(e)), _) => Verdict::Rejected(e.to_string()),
+                (Ok(Ok(actual)), None) => Verdict::Missing { actual },
+                (Ok(Ok(actual)), Some(expected)) if *expected == actual => Verdict::Pass,
+                (Ok(Ok(actual)), Some(expected)) => Verdict::Fail {
+                    expected: expected.clone(),
+                    actual,
+                },
+            };
+
+            Some(Check {
+                year: day.year,
+                day: day.day,
+                part,
+                input: set.input.clone(),
+                verdict,
+                elapsed,
+            })
+        })
+        .collect()
+}
+
+/// The message a panic was raised with.
+pub fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => panic
+            .downcast_ref::<&str>()
+            .map_or("unknown panic".to_string(), |m| m.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_manifests() {
+        let manifest = Manifest::from_json(
+            r#"{"2016": {"day_02": [{"input": "a.txt", "part_1": "1985"}, {"input": "b.txt", "part_2": "5DB3"}]}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                AnswerSet {
+                    input: "a.txt".to_string(),
+                    answers: [Some("1985".to_string()), None],
+                },
+                AnswerSet {
+                    input: "b.txt".to_string(),
+                    answers: [None, Some("5DB3".to_string())],
+                },
+            ],
+            manifest.sets(solutions::day(2016, 2).unwrap())
+        );
+        assert_eq!(
+            "inputs/2016/day_03.txt",
+            manifest.sets(solutions::day(2016, 3).unwrap())[0].input
+        );
+
+        let error = |json| Manifest::from_json(json).unwrap_err().to_string();
+        assert_eq!(
+            "unknown day `2016.day_26`",
+            error(r#"{"2016": {"day_26": []}}"#)
+        );
+        assert_eq!(
+            "unknown day `2014.day_02`",
+            error(r#"{"2014": {"day_02": []}}"#)
+        );
+        assert_eq!(
+            "missing or invalid `2016.day_02.part_1`",
+            error(r#"{"2016": {"day_02": [{"input": "a.txt", "part_1": 1985}]}}"#)
+        );
+        assert!(error("[").starts_with("invalid JSON"));
+    }
+
+    #[test]
+    fn checks_every_part() {
+        let set = AnswerSet {
+            input: "sample.txt".to_string(),
+            answers: [Some("1985".to_string()), None],
+        };
+        let checks = verify(
+            solutions::day(2016, 2).unwrap(),
+            &set,
+            "ULL\nRRDDD\nLURDL\nUUUUD\n",
+        );
+        assert_eq!(Verdict::Pass, checks[0].verdict);
+        assert_eq!(
+            Verdict::Missing {
+                actual: "5DB3".to_string()
+            },
+            checks[1].verdict
+        );
+
+        let set = AnswerSet {
+            answers: [Some("1986".to_string()), None],
+            ..set
+        };
+        let checks = verify(
+            solutions::day(2016, 2).unwrap(),
+            &set,
+            "ULL\nRRDDD\nLURDL\nUUUUD\n",
+        );
+        assert_eq!(
+            Verdict::Fail {
+                expected: "1986".to_string(),
+                actual: "1985".to_string()
+            },
+            checks[0].verdict
+        );
+
+        let checks = verify(solutions::day(2016, 19).unwrap(), &set, "many elves");
+        assert_eq!(
+            Verdict::Rejected(
+                "invalid input, expected the number of elves, found `many elves`".to_string()
+            ),
+            checks[0].verdict
+        );
+
+        let checks = verify(solutions::day(2016, 8).unwrap(), &set, "rect 60x7");
+        assert!(matches!(checks[0].verdict, Verdict::Panicked(_)));
+    }
+
+    #[test]
+    fn leaves_out_parts_the_day_does_not_have() {
+        let day_25 = solutions::day(2016, 25).unwrap();
+        let clock = "out 0\nout 1\njnz 1 -2";
+        let set = AnswerSet {
+            input: "clock.txt".to_string(),
+            answers: [Some("0".to_string()), None],
+        };
+
+        let checks = verify(day_25, &set, clock);
+        assert_eq!(1, checks.len());
+        assert_eq!(Verdict::Pass, checks[0].verdict);
+
+        let set = AnswerSet {
+            answers: [None, Some("Merry Christmas!".to_string())],
+            ..set
+        };
+        let checks = verify(day_25, &set, clock);
+        assert_eq!(
+            Verdict::Rejected("there is no part 2 that day".to_string()),
+            checks[1].verdict
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use std::error::Error;
+use std::io::Read;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use std::{env, fs, io, panic, process};
+
+use rayon::prelude::*;
+
+use advent_of_code_2016::answers::{self, AnswerSet, Check, Manifest, Verdict};
+use advent_of_code_2016::solutions::{self, Day, SolveError};
+
+/// Binary to run the solution of any day, printing the answers and how long they took.
+///
+/// # How to run
+///
+/// This will run both parts of "Day 12" of the most recent year on `./inputs/<year>/day_12.txt`,
+/// or of 2016 on `./inputs/2016/day_12.txt`:
+/// ```shell
+/// $ cargo run --release --bin aoc -- 12
+/// $ cargo run --release --bin aoc -- 12 --year 2016
+/// ```
+///
+/// This will run part 2 of "Day 12" on another input, `-` reading it from stdin. Input the day rejects
+/// is reported as a problem parsing it, a solution panicking on it as such:
+/// ```shell
+/// $ cargo run --release --bin aoc -- 12 --part 2 --input path/to/input.txt
+/// $ cat path/to/input.txt | cargo run --release --bin aoc -- 12 --part 2 --input -
+/// ```
+///
+/// This will run every day of every year on its input in `./inputs`, optionally running the days in parallel,
+/// printing a line for every part also when it fails and exiting with an error when any did:
+/// ```shell
+/// $ cargo run --release --bin aoc -- all
+/// $ cargo run --release --bin aoc -- all --parallel
+/// ```
+///
+/// This will check every day against the answers in `./answers.json`, or in another manifest, for all
+/// inputs listed there, exiting with an error when any answer is wrong:
+/// ```shell
+/// $ cargo run --release --bin aoc -- verify
+/// $ cargo run --release --bin aoc -- verify --answers path/to/answers.json --parallel
+/// ```
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let config = Config::build(&args).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+
+    if let Err(e) = run(config) {
+        eprintln!("Application error: {e}");
+        process::exit(1);
+    }
+}
+
+enum Config {
+    Day {
+        day: &'static Day,
+        /// The part to run, or else every part the day has.
+        part: Option<u8>,
+        input: Option<String>,
+    },
+    All {
+        parallel: bool,
+    },
+    Verify {
+        answers: String,
+        parallel: bool,
+    },
+}
+
+impl Config {
+    fn build(args: &[String]) -> Result<Config, &'static str> {
+        if args.len() < 2 {
+            return Err("missing day, expected a number, `all` or `verify`");
+        }
+
+        if args[1] == "all" {
+            return match args[2..] {
+                [] => Ok(Config::All { parallel: false }),
+                [ref flag] if flag == "--parallel" => Ok(Config::All { parallel: true }),
+                _ => Err("unexpected argument, expected `--parallel`"),
+            };
+        }
+
+        if args[1] == "verify" {
+            let mut answers = "./answers.json".to_string();
+            let mut parallel = false;
+
+            let mut options = args[2..].iter();
+            while let Some(option) = options.next() {
+                match option.as_str() {
+                    "--answers" => answers = options.next().ok_or("missing option value")?.clone(),
+                    "--parallel" => parallel = true,
+                    _ => return Err("unexpected option, expected `--answers` or `--parallel`"),
+                }
+            }
+
+            return Ok(Config::Verify { answers, parallel });
+        }
+
+        let day = u8::from_str(&args[1]).map_err(|_| "invalid day")?;
+        let mut year = solutions::latest_year();
+        let mut part = None;
+        let mut input = None;
+
+        let mut options = args[2..].iter();
+        while let Some(option) = options.next() {
+            let value = options.next().ok_or("missing option value")?;
+            match option.as_str() {
+                "--part" => match value.as_str() {
+                    "1" | "2" => part = Some(u8::from_str(value).unwrap()),
+                    _ => return Err("invalid part, expected 1 or 2"),
+                },
+                "--input" => input = Some(value.clone()),
+                "--year" => year = u16::from_str(value).map_err(|_| "invalid year")?,
+                _ => return Err("unexpected option, expected `--part`, `--input` or `--year`"),
+            }
+        }
+        let day = solutions::day(year, day).ok_or("invalid day, or not solved that year")?;
+
+        Ok(Config::Day { day, part, input })
+    }
+}
+
+fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    match config {
+        Config::Day { day, part, input } => {
+            let input = match input.as_deref() {
+                Some("-") => {
+                    let mut input = String::new();
+                    io::stdin().read_to_string(&mut input)?;
+                    input
+                }
+                Some(path) => fs::read_to_string(path)?,
+                None => day.read_input()?,
+            };
+
+            for each in part.map_or(vec![1, 2], |part| vec![part]) {
+                match report(day, each, &input)? {
+                    Some(line) => println!("{}", line),
+                    None if part.is_some() => return Err(SolveError::NoSuchPart(each).into()),
+                    None => {}
+                }
+            }
+        }
+        Config::All { parallel } => {
+            let days = solutions::days().collect::<Vec<_>>();
+
+            let start = Instant::now();
+            let reports: Vec<_> = match parallel {
+                true => days.par_iter().map(|day| report_day(day)).collect(),
+                false => days.iter().map(|day| report_day(day)).collect(),
+            };
+
+            let mut failed = 0;
+            for line in reports.into_iter().flatten() {
+                match line {
+                    Ok(line) => println!("{}", line),
+                    Err(line) => {
+                        failed += 1;
+                        println!("{}", line);
+                    }
+                }
+            }
+            println!("Total: {}", format_duration(start.elapsed()));
+
+            if failed > 0 {
+                return Err(format!("{} parts failed", failed).into());
+            }
+        }
+        Config::Verify { answers, parallel } => {
+            let manifest = Manifest::from_json(&fs::read_to_string(answers)?)?;
+            let sets = solutions::days()
+                .flat_map(|day| manifest.sets(day).into_iter().map(move |set| (day, set)))
+                .collect::<Vec<_>>();
+
+            let read = |day: &Day, path: &str| match path == day.input_path() {
+                true => day.read_input(),
+                false => fs::read_to_string(path),
+            };
+            let verify = |(day, set): &(&Day, AnswerSet)| match read(day, &set.input) {
+                Ok(input) if input.trim().is_empty() => Err(format!(
+                    "{} day {:0>2}: MISSING {} (empty input)",
+                    day.year, day.day, set.input
+                )),
+                Ok(input) => Ok(answers::verify(day, set, &input)),
+                Err(e) => Err(format!(
+                    "{} day {:0>2}: MISSING {} ({})",
+                    day.year, day.day, set.input, e
+                )),
+            };
+            let results = match parallel {
+                true => sets.par_iter().map(verify).collect(),
+                false => sets.iter().map(verify).collect::<Vec<_>>(),
+            };
+
+            let (mut passed, mut failed, mut missing) = (0, 0, 0);
+            for result in &results {
+                match result {
+                    Ok(checks) => {
+                        for check in checks {
+                            match check.verdict {
+                                Verdict::Pass => passed += 1,
+                                Verdict::Fail { .. }
+                                | Verdict::Rejected(_)
+                                | Verdict::Panicked(_) => failed += 1,
+                                Verdict::Missing { .. } => missing += 1,
+                            }
+                            println!("{}", format_check(check));
+                        }
+                    }
+                    Err(error) => {
+                        missing += 2;
+                        println!("{}", error);
+                    }
+                }
+            }
+            println!("{} passed, {} failed, {} missing", passed, failed, missing);
+
+            if failed > 0 {
+                return Err(format!("{} wrong answers", failed).into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The lines of [`report`] for every part of the day on its own input, prefixed with the day and
+/// part when failing, or a single line when the input can't be read.
+fn report_day(day: &Day) -> Vec<Result<String, String>> {
+    let input = match day.read_input() {
+        Ok(input) => input,
+        Err(e) => return vec![Err(format!("{} day {:0>2}: {}", day.year, day.day, e))],
+    };
+
+    [1, 2]
+        .into_iter()
+        .filter_map(|part| match report(day, part, &input) {
+            Ok(line) => line.map(Ok),
+            Err(e) => Some(Err(format!(
+                "{} day {:0>2} part {}: {}",
+                day.year, day.day, part, e
+            ))),
+        })
+        .collect()
+}
+
+/// Solves the part, returning a line with its answer and how long it took, `None` when the day has
+/// no such part, or else why the input was rejected or how the solution panicked on it.
+///
+/// The panic is still reported by the panic hook, with its backtrace when enabled.
+fn report(day: &Day, part: u8, input: &str) -> Result<Option<String>, String> {
+    let start = Instant::now();
+    let answer = match panic::catch_unwind(|| day.solve(part, input)) {
+        Ok(Ok(answer)) => answer,
+        Ok(Err(SolveError::NoSuchPart(_))) => return Ok(None),
+        Ok(Err(SolveError::Input(e))) => return Err(format!("Problem parsing input: {}", e)),
+        Ok(Err(e)) => return Err(e.to_string()),
+        Err(panic) => {
+            let message = answers::panic_message(panic);
+            return Err(format!("solution panicked: {}", message));
+        }
+    };
+    let elapsed = start.elapsed();
+
+    Ok(Some(format!(
+        "{} day {:0>2} part {}: {} ({})",
+        day.year,
+        day.day,
+        part,
+        answer,
+        format_duration(elapsed)
+    )))
+}
+
+/// A line with the verdict of the check, its input and how long it took.
+fn format_check(check: &Check) -> String {
+    let verdict = match &check.verdict {
+        Verdict::Pass => "PASS".to_string(),
+        Verdict::Fail { expected, actual } => {
+            format!("FAIL expected {:?}, got {:?}", expected, actual)
+        }
+        Verdict::Missing { actual } => format!("MISSING got {:?}", actual),
+        Verdict::Rejected(reason) => format!("FAIL rejected: {}", reason),
+        Verdict::Panicked(message) => format!("FAIL panicked: {}", message),
+    };
+
+    format!(
+        "{} day {:0>2} part {}: {} {} ({})",
+        check.year,
+        check.day,
+        check.part,
+        verdict,
+        check.input,
+        format_duration(check.elapsed)
+    )
+}
+
+fn format_duration(duration: Duration) -> String {
+    match duration.as_micros() {
+        0..=999 => format!("{}µs", duration.as_micros()),
+        1_000..=999_999 => format!("{:.2}ms", duration.as_secs_f64() * 1_000.0),
+        _ => format!("{:.2}s", duration.as_secs_f64()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_problems_parsing_input_apart_from_panics() {
+        let day = |day| solutions::day(2016, day).unwrap();
+
+        assert!(report(day(19), 1, "5\n")
+            .unwrap()
+            .unwrap()
+            .starts_with("2016 day 19 part 1: 3 ("));
+        assert_eq!(Ok(None), report(day(25), 2, "out 0\nout 1\njnz 1 -2"));
+        assert_eq!(
+            Err("Problem parsing input: the input is empty".to_string()),
+            report(day(2), 1, "")
+        );
+        assert_eq!(
+            Err("Problem parsing input: expected the number of elves, found `five`".to_string()),
+            report(day(19), 2, "five")
+        );
+        assert!(report(day(8), 1, "many pixels")
+            .unwrap_err()
+            .starts_with("Problem parsing input: "));
+        // The screen is only 50 pixels wide
+        assert!(report(day(8), 1, "rect 60x7")
+            .unwrap_err()
+            .starts_with("solution panicked: "));
+    }
+}
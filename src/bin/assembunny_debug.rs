@@ -0,0 +1,454 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::io::{BufRead, Write};
+use std::ops::Not;
+use std::str::FromStr;
+use std::{env, fs, io, process};
+
+use advent_of_code_2016::computer::{parse_program, Computer, Register, Snapshot};
+
+/// Binary to interactively debug an assembunny program.
+///
+/// # How to run
+///
+/// This will load the program for "Day 23", with register `a` initialised to 7:
+/// ```shell
+/// $ cargo run --bin assembunny_debug -- ./inputs/2016/day_23.txt a=7
+/// ```
+///
+/// Alternatively the binary can be built and called directly:
+/// ```shell
+/// $ cargo build --release
+/// $ ./target/release/assembunny_debug ./inputs/2016/day_23.txt a=7
+/// ```
+///
+/// Once loaded, commands are read from standard input, type `help` to list them.
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let config = Config::build(&args).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+
+    if let Err(e) = run(config) {
+        eprintln!("Application error: {e}");
+        process::exit(1);
+    }
+}
+
+struct Config {
+    path: String,
+    overrides: Vec<(Register, i64)>,
+}
+
+impl Config {
+    fn build(args: &[String]) -> Result<Config, &'static str> {
+        if args.len() < 2 {
+            return Err("missing program path");
+        }
+        let path = args[1].clone();
+
+        let overrides = args[2..]
+            .iter()
+            .map(|arg| parse_assignment(arg.split('=')))
+            .collect::<Option<Vec<_>>>()
+            .ok_or("invalid register override, expected e.g. a=7")?;
+
+        Ok(Config { path, overrides })
+    }
+}
+
+fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    let program = parse_program(&fs::read_to_string(&config.path)?)?;
+    let file = program.architecture().registers;
+    if let Some((register, _)) = config.overrides.iter().find(|(r, _)| !file.contains(*r)) {
+        return Err(format!("unknown register `{}`", register).into());
+    }
+    let computer = Computer::from_program(program, config.overrides).with_history(HISTORY_LIMIT);
+    let mut debugger = Debugger::new(computer);
+
+    println!("Loaded {}, type `help` for a list of commands", config.path);
+    debugger.print_listing();
+    debugger.print_state();
+
+    let stdin = io::stdin();
+    loop {
+        print!("(debug) ");
+        io::stdout().flush()?;
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        match words.as_slice() {
+            [] => {}
+            ["q" | "quit"] => return Ok(()),
+            ["h" | "help"] => print!("{}", HELP),
+            ["s" | "step"] => debugger.step(1),
+            ["s" | "step", n] => match usize::from_str(n) {
+                Ok(n) => debugger.step(n),
+                Err(_) => println!("Invalid step count `{}`", n),
+            },
+            ["back"] => debugger.step_back(1),
+            ["back", n] => match usize::from_str(n) {
+                Ok(n) => debugger.step_back(n),
+                Err(_) => println!("Invalid step count `{}`", n),
+            },
+            ["c" | "continue"] => debugger.resume(false),
+            ["o" | "output"] => debugger.resume(true),
+            ["b" | "break", ip] => match usize::from_str(ip) {
+                Ok(ip) => debugger.toggle_breakpoint(ip),
+                Err(_) => println!("Invalid instruction index `{}`", ip),
+            },
+            ["w" | "watch", register] => match debugger.register(register) {
+                Some(register) => debugger.toggle_watch(register),
+                None => println!("Unknown register `{}`", register),
+            },
+            ["set", name, value] => match parse_assignment([*name, *value].into_iter()) {
+                Some((register, value)) if debugger.register(name).is_some() => {
+                    debugger.set(register, value)
+                }
+                _ => println!("Invalid assignment `{} {}`", name, value),
+            },
+            ["save", path] => debugger.save(path),
+            ["load", path] => debugger.load(path),
+            ["r" | "registers"] => debugger.print_state(),
+            ["l" | "list"] => debugger.print_listing(),
+            ["a" | "analyze", register] => match debugger.register(register) {
+                Some(register) => debugger.print_analysis(register),
+                None => println!("Unknown register `{}`", register),
+            },
+            _ => println!(
+                "Unknown command `{}`, type `help` for a list of commands",
+                line.trim()
+            ),
+        }
+    }
+}
+
+fn parse_assignment<'a>(mut parts: impl Iterator<Item = &'a str>) -> Option<(Register, i64)> {
+    let register = Register::from_name(parts.next()?)?;
+    let value = i64::from_str(parts.next()?).ok()?;
+
+    parts.next().is_none().then_some((register, value))
+}
+
+struct Debugger {
+    computer: Computer,
+    original: Vec<String>,
+    listing: Vec<String>,
+    breakpoints: BTreeSet<usize>,
+    watches: BTreeSet<Register>,
+}
+
+impl Debugger {
+    fn new(computer: Computer) -> Self {
+        let original = computer.listing();
+
+        Self {
+            computer,
+            listing: original.clone(),
+            original,
+            breakpoints: BTreeSet::new(),
+            watches: BTreeSet::new(),
+        }
+    }
+
+    fn step(&mut self, count: usize) {
+        for _ in 0..count {
+            if let Some(reason) = self.advance() {
+                println!("{}", reason);
+                break;
+            }
+        }
+        self.print_state();
+    }
+
+    fn step_back(&mut self, count: usize) {
+        for _ in 0..count {
+            if self.computer.step_back().not() {
+                println!("No earlier steps remembered");
+                break;
+            }
+        }
+        self.refresh_listing();
+        self.print_state();
+    }
+
+    /// Keeps stepping until the program halts, hits a breakpoint, changes a watched register
+    /// or (when `until_output` is set) emits a value with `out`.
+    fn resume(&mut self, until_output: bool) {
+        let output_len = self.computer.output.len();
+
+        loop {
+            if let Some(reason) = self.advance() {
+                println!("{}", reason);
+                break;
+            }
+            if until_output && self.computer.output.len() > output_len {
+                println!("Output {}", self.computer.output.last().unwrap());
+                break;
+            }
+            if self.breakpoints.contains(&self.computer.ip()) {
+                println!("Breakpoint at {}", self.computer.ip());
+                break;
+            }
+        }
+        self.print_state();
+    }
+
+    /// Executes a single instruction, returns why execution should stop (if it should).
+    fn advance(&mut self) -> Option<String> {
+        if self.computer.is_halted() {
+            return Some("Program halted".to_string());
+        }
+        if self.computer.is_waiting() {
+            return Some("Program waiting for input".to_string());
+        }
+
+        let toggling = self.listing[self.computer.ip()].starts_with("tgl");
+        let watched = self.watched_values();
+
+        if let Err(e) = self.computer.step(false) {
+            return Some(format!("Program stopped: {}", e));
+        }
+
+        if toggling {
+            self.refresh_listing();
+        }
+
+        let changed = self
+            .watches
+            .iter()
+            .zip(watched)
+            .filter(|(register, value)| self.computer.registers[register] != *value)
+            .map(|(register, value)| {
+                format!(
+                    "Watched register {} changed: {} -> {}",
+                    register, value, self.computer.registers[register]
+                )
+            })
+            .collect::<Vec<_>>();
+
+        changed.is_empty().not().then(|| changed.join("\n"))
+    }
+
+    fn refresh_listing(&mut self) {
+        if self.listing != self.computer.listing() {
+            self.listing = self.computer.listing();
+            println!("Instruction list changed");
+            self.print_listing();
+        }
+    }
+
+    fn watched_values(&self) -> Vec<i64> {
+        self.watches
+            .iter()
+            .map(|register| self.computer.registers[register])
+            .collect()
+    }
+
+    fn toggle_breakpoint(&mut self, ip: usize) {
+        if self.breakpoints.remove(&ip) {
+            println!("Removed breakpoint at {}", ip);
+        } else {
+            self.breakpoints.insert(ip);
+            println!("Added breakpoint at {}", ip);
+        }
+    }
+
+    fn toggle_watch(&mut self, register: Register) {
+        if self.watches.remove(&register) {
+            println!("Stopped watching register {}", register);
+        } else {
+            self.watches.insert(register);
+            println!("Watching register {}", register);
+        }
+    }
+
+    /// The register with that name, when the program has it.
+    fn register(&self, name: &str) -> Option<Register> {
+        let file = self.computer.registers.file();
+
+        Register::from_name(name).filter(|r| file.contains(*r))
+    }
+
+    fn set(&mut self, register: Register, value: i64) {
+        self.computer.registers[&register] = self.computer.architecture().word.wrap(value);
+        self.print_state();
+    }
+
+    fn save(&self, path: &str) {
+        match fs::write(path, self.computer.snapshot().to_json()) {
+            Ok(()) => println!("Saved snapshot to {}", path),
+            Err(e) => println!("Could not save snapshot: {}", e),
+        }
+    }
+
+    /// Continues from a saved snapshot, forgetting the earlier steps. Its program becomes the one
+    /// toggles are compared with.
+    fn load(&mut self, path: &str) {
+        let snapshot = fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|json| Snapshot::from_json(&json).map_err(|e| e.to_string()));
+
+        match snapshot {
+            Ok(snapshot) => {
+                self.computer = Computer::restore(snapshot).with_history(HISTORY_LIMIT);
+                self.original = self.computer.listing();
+                self.listing = self.original.clone();
+                println!("Loaded snapshot from {}", path);
+                self.print_listing();
+                self.print_state();
+            }
+            Err(e) => println!("Could not load snapshot: {}", e),
+        }
+    }
+
+    fn print_state(&self) {
+        let registers = self
+            .computer
+            .registers
+            .file()
+            .registers()
+            .iter()
+            .map(|r| format!("{}={}", r, self.computer.registers[r]))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let current = self
+            .listing
+            .get(self.computer.ip())
+            .cloned()
+            .unwrap_or_else(|| "halted".to_string());
+
+        println!(
+            "ip={} [{}] {} output={:?}",
+            self.computer.ip(),
+            current,
+            registers,
+            self.computer.output
+        );
+    }
+
+    /// Prints the closed forms of the rest of the run, in terms of the register's value.
+    fn print_analysis(&self, register: Register) {
+        match self.computer.analyze(register, ANALYSIS_STEPS) {
+            Ok(analysis) => print!("{}", analysis),
+            Err(e) => println!("Analysis failed: {}", e),
+        }
+    }
+
+    /// Prints the current instructions, marking the instruction pointer (`>`), breakpoints (`*`)
+    /// and instructions that got toggled compared to the original program.
+    fn print_listing(&self) {
+        for line in self.listing_lines() {
+            println!("{}", line);
+        }
+    }
+
+    fn listing_lines(&self) -> Vec<String> {
+        self.listing
+            .iter()
+            .enumerate()
+            .map(|(idx, instruction)| {
+                let ip = if idx == self.computer.ip() { '>' } else { ' ' };
+                let breakpoint = if self.breakpoints.contains(&idx) {
+                    '*'
+                } else {
+                    ' '
+                };
+                let toggled = match self.original.get(idx) {
+                    Some(original) if original != instruction => format!("  (was: {})", original),
+                    _ => String::new(),
+                };
+
+                format!(
+                    "{}{} {:>3}  {}{}",
+                    ip, breakpoint, idx, instruction, toggled
+                )
+            })
+            .collect()
+    }
+}
+
+/// The number of steps `back` can undo.
+const HISTORY_LIMIT: usize = 100_000;
+
+/// Step limit of the symbolic execution behind the `analyze` command.
+const ANALYSIS_STEPS: usize = 1_000_000;
+
+const HELP: &str = "\
+Commands:
+  s, step [n]          execute n instructions (default 1)
+  back [n]             undo the last n steps (default 1)
+  c, continue          run until halted, a breakpoint or a watched register change
+  o, output            run until the next value is output
+  b, break <ip>        toggle a breakpoint on an instruction index
+  w, watch <register>  toggle a watch, stopping when the register changes
+  set <register> <v>   change the value of a register
+  save <path>          write the whole machine state to a JSON file
+  load <path>          continue from a state written by `save`
+  r, registers         show the instruction pointer, registers and output
+  l, list              show the (possibly toggled) instructions
+  a, analyze <reg>     closed forms for the rest of the run, in terms of the register's value
+  h, help              show this help
+  q, quit              exit the debugger
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn debugger(source: &str) -> Debugger {
+        Debugger::new(Computer::from_program(
+            parse_program(source).unwrap(),
+            vec![],
+        ))
+    }
+
+    #[test]
+    fn lists_toggled_instructions() {
+        let mut debugger = debugger("cpy 2 a\ntgl a\ninc b\ninc b");
+        debugger.step(2);
+
+        assert_eq!(
+            vec![
+                "     0  cpy 2 a",
+                "     1  tgl a",
+                ">    2  inc b",
+                "     3  dec b  (was: inc b)",
+            ],
+            debugger.listing_lines()
+        );
+    }
+
+    #[test]
+    fn loads_snapshots_of_longer_programs() {
+        let dir = env::temp_dir().join(format!("assembunny-debug-loads-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("snapshot.json");
+
+        let mut longer = debugger("cpy 2 a\ntgl a\ninc b\ninc b\ninc c\ninc d");
+        longer.step(2);
+        longer.save(path.to_str().unwrap());
+
+        let mut debugger = debugger("inc a");
+        debugger.load(path.to_str().unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            vec![
+                "     0  cpy 2 a",
+                "     1  tgl a",
+                ">    2  inc b",
+                "     3  dec b",
+                "     4  inc c",
+                "     5  inc d",
+            ],
+            debugger.listing_lines()
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use std::error::Error;
+use std::{env, fs, process};
+
+use advent_of_code_2016::computer::{assemble, transpile};
+
+/// Binary to translate an assembunny program into an equivalent Rust function.
+///
+/// # How to run
+///
+/// This will print the program for "Day 12" as a Rust function named `day_12`:
+/// ```shell
+/// $ cargo run --bin assembunny_to_rust -- ./inputs/2016/day_12.txt day_12
+/// ```
+///
+/// Alternatively the binary can be built and called directly:
+/// ```shell
+/// $ cargo build --release
+/// $ ./target/release/assembunny_to_rust ./inputs/2016/day_12.txt day_12
+/// ```
+///
+/// The function is named `run` when no name is given. It takes the initial registers and the maximum
+/// output length, and returns the final registers and the output, like `Computer::run` does.
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let config = Config::build(&args).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+
+    if let Err(e) = run(config) {
+        eprintln!("Application error: {e}");
+        process::exit(1);
+    }
+}
+
+struct Config {
+    path: String,
+    name: String,
+}
+
+impl Config {
+    fn build(args: &[String]) -> Result<Config, &'static str> {
+        if args.len() < 2 {
+            return Err("missing program path");
+        }
+        let path = args[1].clone();
+        let name = args.get(2).cloned().unwrap_or_else(|| "run".to_string());
+
+        let identifier = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !identifier {
+            return Err("invalid function name");
+        }
+
+        Ok(Config { path, name })
+    }
+}
+
+fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    let program = assemble(&fs::read_to_string(&config.path)?)?;
+
+    print!("{}", transpile(&program, &config.name)?);
+
+    Ok(())
+}
@@ -1,24 +1,40 @@
 use std::error::Error;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::Write;
 use std::ops::Not;
+use std::path::Path;
 use std::str::FromStr;
 use std::{env, process};
 
-use chrono::Datelike;
+use advent_of_code_2016::puzzle::{self, Client, PuzzleError};
+
+/// The sample of the tests when the problem has none, to replace by hand.
+const NO_SAMPLE: &str = "TODO: the sample";
 
 /// Binary to scaffold code for a new Advent of Code day.
 ///
 /// # How to run
 ///
-/// This will scaffold all code for the day it is ran on:
+/// This will scaffold all code for the puzzle unlocked on the day it is ran on, which has to be in
+/// December, in the puzzles' time zone (UTC-5):
 /// ```shell
 /// $ cargo run --bin scaffold
 /// ```
 ///
-/// This will scaffold all code for "Day 07":
+/// This will scaffold all code for "Day 07" of the most recent year, or of 2016:
 /// ```shell
 /// $ cargo run --bin scaffold -- 7
+/// $ cargo run --bin scaffold -- 7 --year 2016
+/// ```
+///
+/// The solution takes the input as lines by default, `--input text` makes it take the whole text:
+/// ```shell
+/// $ cargo run --bin scaffold -- 7 --input text
+/// ```
+///
+/// Once part 1 is solved, this will update the problem statement with part 2, and nothing else:
+/// ```shell
+/// $ cargo run --bin scaffold -- 7 --problem
 /// ```
 ///
 /// Alternatively the binary can be built and called directly:
@@ -30,6 +46,20 @@ use chrono::Datelike;
 /// Optionally an environment variable `OVERWRITE` can be set,
 /// this will overwrite contents of existing files,
 /// normally existing files would throw an error.
+///
+/// The input is downloaded with the session token in the environment variable `AOC_SESSION`,
+/// or in `./aoc.json` (see `puzzle::CONFIG_PATH`), and cached under `./.cache` so it is only
+/// downloaded once. Without a token the input is left empty.
+///
+/// The problem statement is converted to Markdown in `./problems/<year>/day_07.md`, which is always
+/// overwritten, and its first code block is the sample of the first test. It is fetched from the
+/// same server, and once it has part 2 also cached.
+///
+/// The solution and its benchmark are generated from the templates in `./templates`, where
+/// `{{year}}`, `{{day}}`, `{{module}}` (`day_07`), `{{name}}` (`Day07`), `{{input_type}}`,
+/// `{{input}}`, `{{parse}}` and `{{sample}}` (a string literal, a placeholder when there is no
+/// sample) are replaced. The day is registered in `./src/solutions/y<year>.rs`,
+/// the year in `./src/solutions.rs` and the benchmark in `./Cargo.toml`, in order and only once.
 fn main() {
     let args: Vec<String> = env::args().collect();
 
@@ -45,45 +75,168 @@ fn main() {
 }
 
 struct Config {
+    year: u16,
     day: u8,
+    input: InputKind,
+    problem_only: bool,
     overwrite: bool,
 }
 
+/// How the scaffolded solution takes its input.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum InputKind {
+    Lines,
+    Text,
+}
+
 impl Config {
     fn build(args: &[String]) -> Result<Config, &'static str> {
-        let day = if args.len() >= 2 {
-            u8::from_str(&args[1])
-        } else {
-            Ok(chrono::prelude::Utc::now().day() as u8)
-        };
-        if day.is_err() {
+        let (today_year, today) = puzzle::puzzle_date(chrono::Utc::now());
+        let mut year = None;
+        let mut day = None;
+        let mut input = InputKind::Lines;
+        let mut problem_only = false;
+
+        let mut args = args[1..].iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" => {
+                    input = match args.next().map(String::as_str) {
+                        Some("lines") => InputKind::Lines,
+                        Some("text") => InputKind::Text,
+                        _ => return Err("invalid input type, expected `lines` or `text`"),
+                    }
+                }
+                "--problem" => problem_only = true,
+                "--year" => {
+                    let value = args.next().ok_or("missing option value")?;
+                    year = Some(u16::from_str(value).map_err(|_| "invalid year")?);
+                }
+                _ => day = Some(u8::from_str(arg).map_err(|_| "invalid day")?),
+            }
+        }
+        let year = year.unwrap_or(today_year);
+        let day = day
+            .or(today)
+            .ok_or("no puzzle unlocked today, give the day")?;
+        if (1..=25).contains(&day).not() {
             return Err("invalid day");
         }
-        let day = day.unwrap();
 
         let overwrite = env::var("OVERWRITE").is_ok();
 
-        Ok(Config { day, overwrite })
+        Ok(Config {
+            year,
+            day,
+            input,
+            problem_only,
+            overwrite,
+        })
+    }
+
+    /// The values of the template placeholders, the sample being non-empty for it to parse.
+    fn placeholders(&self, sample: Option<&str>) -> Vec<(&'static str, String)> {
+        let (input_type, input, parse) = match self.input {
+            InputKind::Lines => ("&[&str]", "Vec<&'a str>", "solutions::lines(input)"),
+            InputKind::Text => ("&str", "&'a str", "solutions::text(input)"),
+        };
+
+        vec![
+            ("year", self.year.to_string()),
+            ("day", self.day.to_string()),
+            ("module", format!("day_{:0>2}", self.day)),
+            ("name", format!("Day{:0>2}", self.day)),
+            ("input_type", input_type.to_string()),
+            ("input", input.to_string()),
+            ("parse", parse.to_string()),
+            (
+                "sample",
+                format!(
+                    "{:?}",
+                    sample
+                        .filter(|s| s.trim().is_empty().not())
+                        .unwrap_or(NO_SAMPLE)
+                ),
+            ),
+        ]
     }
 }
 
 fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let day = format!("day_{:0>2}", config.day);
+    let (year, day) = (config.year, format!("day_{:0>2}", config.day));
+
+    let client = Client::from_config()?;
+    let problem_path = format!("./problems/{}/{}.md", year, day);
+    let problem = match client.problem(year, config.day) {
+        Ok(problem) => {
+            fs::create_dir_all(Path::new(&problem_path).parent().unwrap())?;
+            fs::write(&problem_path, &problem.markdown)?;
+            println!(
+                "Wrote {} part(s) of the problem to {}",
+                problem.parts, problem_path
+            );
+            Some(problem)
+        }
+        Err(e) if config.problem_only.not() => {
+            eprintln!("Leaving the problem empty, {}", e);
+            None
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if config.problem_only {
+        return Ok(());
+    }
+    let placeholders = config.placeholders(problem.and_then(|p| p.sample).as_deref());
+
+    let input = match client.has_session() {
+        true => client.input(year, config.day)?,
+        false => {
+            eprintln!("Leaving the input empty, {}", PuzzleError::MissingSession);
+            String::new()
+        }
+    };
 
-    write_file(&config, &format!("./inputs/{}.txt", day), "")?;
-    write_file(&config, &format!("./problems/{}.txt", day), "")?;
+    write_file(&config, &format!("./inputs/{}/{}.txt", year, day), &input)?;
     write_file(
         &config,
-        &format!("./src/solutions/{}.rs", day),
-        &TEMPLATE.replace("{}", &day),
+        &format!("./src/solutions/y{}/{}.rs", year, day),
+        &render(&placeholders, "./templates/solution.rs.template")?,
+    )?;
+    write_file(
+        &config,
+        &format!("./benches/y{}_{}_bench.rs", year, day),
+        &render(&placeholders, "./templates/bench.rs.template")?,
     )?;
 
-    append_file("./src/solutions.rs", &format!("pub mod {};\n", day))?;
+    let year_module = format!("./src/solutions/y{}.rs", year);
+    if Path::new(&year_module).exists().not() {
+        fs::write(&year_module, year_module_source(year))?;
+    }
+    update_file(&year_module, |source| register_day(source, config.day))?;
+    update_file("./src/solutions.rs", |source| register_year(source, year))?;
+    update_file("./Cargo.toml", |manifest| {
+        Ok(register_bench(manifest, &format!("y{}_{}", year, day)))
+    })?;
 
     Ok(())
 }
 
+fn render(placeholders: &[(&str, String)], template: &str) -> Result<String, Box<dyn Error>> {
+    let template =
+        fs::read_to_string(template).map_err(|e| format!("template {}: {}", template, e))?;
+
+    Ok(placeholders
+        .iter()
+        .fold(template, |rendered, (name, value)| {
+            rendered.replace(&format!("{{{{{}}}}}", name), value)
+        }))
+}
+
 fn write_file(config: &Config, path: &str, content: &str) -> Result<(), Box<dyn Error>> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+
     File::options()
         .create_new(config.overwrite.not())
         .write(true)
@@ -94,56 +247,224 @@ fn write_file(config: &Config, path: &str, content: &str) -> Result<(), Box<dyn
     Ok(())
 }
 
-fn append_file(path: &str, content: &str) -> Result<(), Box<dyn Error>> {
-    File::options()
-        .write(true)
-        .append(true)
-        .open(path)?
-        .write_all(content.as_ref())?;
+fn update_file(
+    path: &str,
+    update: impl Fn(&str) -> Result<String, String>,
+) -> Result<(), Box<dyn Error>> {
+    let content = fs::read_to_string(path)?;
+    let updated = update(&content).map_err(|e| format!("{}: {}", path, e))?;
+    if updated != content {
+        fs::write(path, updated)?;
+    }
 
     Ok(())
 }
 
-const TEMPLATE: &str = "\
-// use std::str::FromStr;
+/// The module of a year without any days yet.
+fn year_module_source(year: u16) -> String {
+    format!(
+        "use super::Day;\n\npub const YEAR: u16 = {};\n\n/// Every day of the year, in order.\npub static DAYS: &[Day] = &[];\n",
+        year
+    )
+}
+
+/// Adds the module of the day, and its entry in `DAYS`, among the other days in order.
+fn register_day(source: &str, day: u8) -> Result<String, String> {
+    let source = register_module(source, &format!("day_{:0>2}", day));
+    let entry = format!("Day::of::<day_{:0>2}::Day{:0>2}>(YEAR, {})", day, day, day);
+
+    register_entry(&source, "DAYS", &entry)
+}
+
+/// Adds the module of the year, and its entry in `YEARS`, among the other years in order.
+fn register_year(source: &str, year: u16) -> Result<String, String> {
+    let source = register_module(source, &format!("y{}", year));
+
+    register_entry(&source, "YEARS", &format!("y{}::DAYS", year))
+}
+
+/// Adds `pub mod` for the module before the first one sorting after it, or else after the last.
+fn register_module(source: &str, module: &str) -> String {
+    let mut lines = source.lines().map(str::to_string).collect::<Vec<_>>();
+    let line = format!("pub mod {};", module);
+    if lines.contains(&line) {
+        return source.to_string();
+    }
+
+    let modules = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.starts_with("pub mod "))
+        .collect::<Vec<_>>();
+    let idx = match modules.iter().find(|(_, l)| **l > line) {
+        Some((idx, _)) => *idx,
+        None => modules.last().map_or(0, |(idx, _)| idx + 1),
+    };
+    // Keeps the modules apart from what follows them
+    if modules.is_empty() {
+        lines.insert(0, String::new());
+    }
+    lines.insert(idx, line);
+
+    lines.join("\n") + "\n"
+}
+
+/// Adds the entry to the static slice, keeping its entries sorted and each only once.
+fn register_entry(source: &str, name: &str, entry: &str) -> Result<String, String> {
+    let missing = || format!("no `pub static {}` slice", name);
+    let start = source
+        .find(&format!("pub static {}: ", name))
+        .ok_or_else(missing)?;
+    let open = start + source[start..].find("= &[").ok_or_else(missing)? + 4;
+    let close = open + source[open..].find("];").ok_or_else(missing)?;
+
+    let mut entries = split_entries(&source[open..close]);
+    if entries.iter().any(|e| e == entry) {
+        return Ok(source.to_string());
+    }
+    entries.push(entry.to_string());
+    entries.sort();
+
+    let list = entries
+        .iter()
+        .map(|e| format!("    {},\n", e))
+        .collect::<String>();
+
+    Ok(format!("{}\n{}{}", &source[..open], list, &source[close..]))
+}
+
+/// The comma separated entries of a list, ignoring commas inside brackets.
+fn split_entries(list: &str) -> Vec<String> {
+    let mut entries = vec![];
+    let (mut depth, mut entry) = (0, String::new());
+
+    for c in list.chars() {
+        match c {
+            '(' | '<' | '[' => depth += 1,
+            ')' | '>' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                entries.push(entry.trim().to_string());
+                entry.clear();
+                continue;
+            }
+            _ => {}
+        }
+        entry.push(c);
+    }
+    entries.push(entry.trim().to_string());
+
+    entries.retain(|e| e.is_empty().not());
+    entries
+}
 
-// use regex::Regex;
+/// Adds a benchmark target, unless it is there already.
+fn register_bench(manifest: &str, prefix: &str) -> String {
+    let name = format!("name = \"{}_bench\"", prefix);
+    if manifest.lines().any(|line| line == name) {
+        return manifest.to_string();
+    }
 
-pub fn solve_1(X: &[&str]) -> u32 {
-    42
+    format!(
+        "{}\n\n[[bench]]\n{}\nharness = false\n",
+        manifest.trim_end(),
+        name
+    )
 }
 
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
 
     #[test]
-    fn {}_part_01_sample() {
-        let sample = vec![
-            \"\",
-            \"\",
-            \"\",
-            \"\",
-            \"\",
-            \"\",
-            \"\",
-            \"\",
-            \"\",
-            \"\",
-        ];
-
-        assert_eq!(42, solve_1(&sample));
+    fn registers_days_in_order_once() {
+        let source = "\
+pub mod day_01;
+pub mod day_03;
+
+use super::Day;
+
+pub static DAYS: &[Day] = &[
+    Day::of::<day_01::Day01>(YEAR, 1),
+    Day::of::<day_03::Day03>(YEAR, 3),
+];
+";
+        let expected = "\
+pub mod day_01;
+pub mod day_02;
+pub mod day_03;
+
+use super::Day;
+
+pub static DAYS: &[Day] = &[
+    Day::of::<day_01::Day01>(YEAR, 1),
+    Day::of::<day_02::Day02>(YEAR, 2),
+    Day::of::<day_03::Day03>(YEAR, 3),
+];
+";
+
+        assert_eq!(Ok(expected.to_string()), register_day(source, 2));
+        assert_eq!(Ok(expected.to_string()), register_day(expected, 2));
+        assert!(register_day(expected, 4)
+            .unwrap()
+            .contains("pub mod day_03;\npub mod day_04;\n"));
+        assert_eq!(
+            Err("no `pub static DAYS` slice".to_string()),
+            register_day("", 1)
+        );
     }
 
     #[test]
-    fn {}_part_01_solution() {
-        let input = include_str!(\"../../inputs/{}.txt\")
-            .lines()
-            .collect_vec();
+    fn registers_new_years() {
+        let module = register_day(&year_module_source(2017), 1).unwrap();
+        assert_eq!(
+            "pub mod day_01;\n\nuse super::Day;\n\npub const YEAR: u16 = 2017;\n\n\
+             /// Every day of the year, in order.\npub static DAYS: &[Day] = &[\n    \
+             Day::of::<day_01::Day01>(YEAR, 1),\n];\n",
+            module
+        );
 
-        assert_eq!(0, solve_1(&input));
+        let solutions = "pub mod y2016;\n\npub static YEARS: &[&[Day]] = &[y2016::DAYS];\n";
+        assert_eq!(
+            Ok(
+                "pub mod y2015;\npub mod y2016;\n\npub static YEARS: &[&[Day]] = &[\n    \
+                y2015::DAYS,\n    y2016::DAYS,\n];\n"
+                    .to_string()
+            ),
+            register_year(solutions, 2015)
+        );
+        assert_eq!(Ok(solutions.to_string()), register_year(solutions, 2016));
+    }
+
+    #[test]
+    fn fills_in_a_sample_that_parses() {
+        let config = Config {
+            year: 2016,
+            day: 7,
+            input: InputKind::Text,
+            problem_only: false,
+            overwrite: false,
+        };
+        let sample = |sample| {
+            config
+                .placeholders(sample)
+                .into_iter()
+                .find(|(name, _)| *name == "sample")
+                .unwrap()
+                .1
+        };
+
+        assert_eq!("\"abba[mnop]\\n\"", sample(Some("abba[mnop]\n")));
+        assert_eq!(format!("{:?}", NO_SAMPLE), sample(None));
+        assert_eq!(format!("{:?}", NO_SAMPLE), sample(Some("\n")));
+    }
+
+    #[test]
+    fn registers_benches_once() {
+        let manifest = "[package]\nname = \"aoc\"\n";
+        let expected =
+            "[package]\nname = \"aoc\"\n\n[[bench]]\nname = \"y2016_day_07_bench\"\nharness = false\n";
+
+        assert_eq!(expected, register_bench(manifest, "y2016_day_07"));
+        assert_eq!(expected, register_bench(expected, "y2016_day_07"));
     }
 }
-";
@@ -0,0 +1,134 @@
+use std::error::Error;
+use std::str::FromStr;
+use std::{env, process};
+
+use advent_of_code_2016::puzzle::Client;
+use advent_of_code_2016::solutions;
+
+/// Binary to submit the answer to a part of an Advent of Code day.
+///
+/// # How to run
+///
+/// This will solve part 1 of "Day 12" of the most recent year on `./inputs/<year>/day_12.txt` and
+/// submit the answer:
+/// ```shell
+/// $ cargo run --release --bin submit -- 12 1
+/// ```
+///
+/// This will submit a given answer instead, optionally for another year:
+/// ```shell
+/// $ cargo run --release --bin submit -- 12 1 318009
+/// $ cargo run --release --bin submit -- 12 1 318009 --year 2016
+/// ```
+///
+/// The server and session token are configured like for `scaffold`. Every attempt is recorded in the
+/// cache directory, `./.cache/aoc/<year>/submissions.json` by default. An answer known to be wrong from
+/// earlier attempts, or outside the bounds they gave, is not submitted.
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let config = Config::build(&args).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+
+    if let Err(e) = run(config) {
+        eprintln!("Application error: {e}");
+        process::exit(1);
+    }
+}
+
+struct Config {
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: Option<String>,
+}
+
+impl Config {
+    fn build(args: &[String]) -> Result<Config, &'static str> {
+        let mut year = solutions::latest_year();
+        let mut positional = vec![];
+
+        let mut args = args[1..].iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--year" => {
+                    let value = args.next().ok_or("missing option value")?;
+                    year = u16::from_str(value).map_err(|_| "invalid year")?;
+                }
+                _ => positional.push(arg.clone()),
+            }
+        }
+        if positional.len() < 2 {
+            return Err("missing day and part");
+        }
+
+        let day = u8::from_str(&positional[0])
+            .ok()
+            .filter(|day| (1..=25).contains(day))
+            .ok_or("invalid day")?;
+        let part = u8::from_str(&positional[1])
+            .ok()
+            .filter(|part| [1, 2].contains(part))
+            .ok_or("invalid part, expected 1 or 2")?;
+        let answer = positional.get(2).cloned();
+        // Without an answer the solution of the day computes it
+        if answer.is_none() && solutions::day(year, day).is_none() {
+            return Err("day not solved that year, give the answer");
+        }
+
+        Ok(Config {
+            year,
+            day,
+            part,
+            answer,
+        })
+    }
+}
+
+fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    let answer = match config.answer {
+        Some(answer) => answer,
+        None => {
+            let day = solutions::day(config.year, config.day)
+                .ok_or("day not solved that year, give the answer")?;
+            let input = day.read_input()?;
+            day.solve(config.part, &input)?
+        }
+    };
+
+    println!("Submitting {}", answer);
+    let feedback = Client::from_config()?.submit(config.year, config.day, config.part, &answer)?;
+    println!("{}", feedback);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(args: &[&str]) -> Result<Config, &'static str> {
+        let args = ["submit"].iter().chain(args).map(|a| a.to_string());
+        Config::build(&args.collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn takes_answers_for_unsolved_days() {
+        let config = build(&["12", "1", "318009", "--year", "2015"]).unwrap();
+        assert_eq!((2015, 12, 1), (config.year, config.day, config.part));
+        assert_eq!(Some("318009".to_string()), config.answer);
+
+        assert_eq!(
+            Err("day not solved that year, give the answer"),
+            build(&["12", "1", "--year", "2015"]).map(|c| c.day)
+        );
+        assert_eq!(Ok(12), build(&["12", "1", "--year", "2016"]).map(|c| c.day));
+        assert_eq!(Err("invalid day"), build(&["26", "1", "1"]).map(|c| c.day));
+        assert_eq!(
+            Err("invalid part, expected 1 or 2"),
+            build(&["12", "3", "1"]).map(|c| c.day)
+        );
+    }
+}
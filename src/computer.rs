@@ -1,112 +1,608 @@
-use itertools::Itertools;
-use rustc_hash::FxHashMap;
-use std::ops::Not;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut, Not};
 use std::str::FromStr;
 
+use rustc_hash::FxHashSet;
+
+pub use assembler::assemble;
+pub use budget::{Outcome, Run, RunConfig};
+pub use network::{Network, NetworkError, NetworkStatus};
+use optimizer::Idiom;
+pub use parser::{parse_program, parse_program_for, ParseError};
+pub use snapshot::{Snapshot, SnapshotError};
+pub use symbolic::{Analysis, SymbolicError};
+pub use tracer::Trace;
+pub use transpiler::{transpile, TranspileError};
+
+mod assembler;
+mod budget;
+mod disassembler;
+pub mod fuzz;
+mod network;
+mod optimizer;
+mod parser;
+pub mod reference;
+mod snapshot;
+mod symbolic;
+pub mod tracer;
+mod transpiler;
+
 #[derive(Debug, Clone)]
 pub struct Computer {
-    pub registers: FxHashMap<Register, i32>,
-    pub output: Vec<i32>,
+    pub registers: Registers,
+    pub input: VecDeque<i64>,
+    pub output: Vec<i64>,
+    architecture: Architecture,
+    ip: usize,
     instructions: Vec<Instruction>,
+    ops: Vec<Op>,
+    idioms: Vec<Option<Idiom>>,
+    trace: Option<Trace>,
+    history: Option<snapshot::History>,
 }
 
 impl Computer {
-    pub fn new(code: &[&str], overrides: Vec<(Register, i32)>) -> Self {
-        let registers = [Register::A, Register::B, Register::C, Register::D]
-            .into_iter()
-            .map(|r| (r, 0))
-            .chain(overrides)
-            .collect();
-        let instructions = code.iter().map(|i| Instruction::new(i)).collect();
+    pub fn new(code: &[&str], overrides: Vec<(Register, i64)>) -> Result<Self, ParseError> {
+        Ok(Self::from_program(
+            parse_program(&code.join("\n"))?,
+            overrides,
+        ))
+    }
+
+    /// Creates a computer for the program's [`Architecture`], the overrides wrap around to fit its word.
+    ///
+    /// Panics when an override is not a register of the architecture.
+    pub fn from_program(program: Program, overrides: Vec<(Register, i64)>) -> Self {
+        let architecture = program.architecture;
+
+        let mut registers = Registers::new(architecture.registers);
+        for (register, value) in overrides {
+            assert!(
+                architecture.registers.contains(register),
+                "no register {}",
+                register
+            );
+            registers[&register] = architecture.word.wrap(value);
+        }
+
+        let instructions = program.instructions;
+        let ops = instructions.iter().map(Op::decode).collect();
+        let idioms = optimizer::detect(&instructions);
 
         Self {
             registers,
+            input: VecDeque::new(),
             output: vec![],
+            architecture,
+            ip: 0,
             instructions,
+            ops,
+            idioms,
+            trace: None,
+            history: None,
+        }
+    }
+
+    /// Enables tracing, from now on every executed step is recorded, see [`Computer::trace`].
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(Trace::new(self.instructions.len()));
+        self
+    }
+
+    /// Queues values for `in` to read, in order.
+    pub fn with_input(mut self, values: impl IntoIterator<Item = i64>) -> Self {
+        self.input.extend(values);
+        self
+    }
+
+    pub fn trace(&self) -> Option<&Trace> {
+        self.trace.as_ref()
+    }
+
+    /// Runs the program until it halts, waits for input, or produced more than `output_len` values.
+    ///
+    /// With `optimize` set, recognised loop idioms (additions and multiplications) are executed
+    /// as single fused operations, yielding the same result as stepping through them.
+    ///
+    /// Fails when a value overflows a [`Word::Checked`] word.
+    pub fn run(mut self, optimize: bool, output_len: usize) -> Result<Self, OverflowError> {
+        while !self.is_halted() && !self.is_waiting() && self.output.len() <= output_len {
+            self.step(optimize)?;
+        }
+
+        Ok(self)
+    }
+
+    /// Lazily runs the program, yielding every value it outputs, until it halts, waits for input (or overflows).
+    pub fn outputs(self, optimize: bool) -> Outputs {
+        Outputs {
+            computer: self,
+            optimize,
+            overflowed: false,
         }
     }
 
-    pub fn run(&self, unwrap_loop: bool, output_len: usize) -> Self {
-        let mut computer = self.clone();
-        let mut ip = 0;
+    /// Checks whether the program outputs the clock signal `0, 1, 0, 1, ...` forever.
+    ///
+    /// The signal is proven to go on forever once the machine reaches the same state (instruction pointer,
+    /// registers, instructions, input and the next expected value) right after an output as it did earlier on.
+    /// When that does not happen within `max_outputs` correct values the check is undecided.
+    ///
+    /// It is disproven as well when the machine gets stuck in a loop that doesn't output anything.
+    pub fn check_clock_signal(self, optimize: bool, max_outputs: usize) -> ClockSignal {
+        let mut states = FxHashSet::default();
+        let mut computer = self;
+        let config = RunConfig {
+            optimize,
+            detect_loops: true,
+            ..RunConfig::default()
+        };
+
+        for idx in 0..max_outputs {
+            let run = match computer.run_with(config.output_len(idx)) {
+                Ok(run) if run.outcome == Outcome::OutputLimit => run,
+                _ => return ClockSignal::Disproven,
+            };
+            computer = run.computer;
+            if computer.output[idx] != (idx % 2) as i64 {
+                return ClockSignal::Disproven;
+            }
 
-        while ip < computer.instructions.len() && computer.output.len() <= output_len {
-            ip = computer.step(ip, unwrap_loop);
+            let state = (
+                computer.ip,
+                computer.registers.clone(),
+                computer.instructions.clone(),
+                computer.input.clone(),
+                idx % 2,
+            );
+            if states.insert(state).not() {
+                return ClockSignal::Proven;
+            }
         }
 
-        computer
+        ClockSignal::Undecided
     }
 
-    fn step(&mut self, ip: usize, unwrap_loop: bool) -> usize {
-        let instruction = &self.instructions[ip];
-        let mut ip = ip;
+    /// Executes the instruction the instruction pointer is at (or a whole loop idiom when optimizing).
+    ///
+    /// On overflow the machine is left as it was before the step, a machine waiting for input doesn't move.
+    pub fn step(&mut self, optimize: bool) -> Result<(), OverflowError> {
+        let ip = self.ip;
+
+        if self.is_waiting() {
+            return Ok(());
+        }
 
-        // Manually unrolled the assembunny for Day 23 Part 2 to replace the loops with the direct code below
-        if ip == 5 && unwrap_loop {
-            self.registers = [
-                (
-                    Register::A,
-                    self.registers[&Register::A]
-                        + self.registers[&Register::B] * self.registers[&Register::D],
-                ),
-                (Register::B, self.registers[&Register::B]),
-                (Register::C, 0),
-                (Register::D, 1),
-            ]
-            .into_iter()
-            .collect();
-            return 8;
+        let undo = self.history.is_some().then(|| self.undo(ip));
+        self.ip = match self.trace {
+            None => self.advance(ip, optimize)?,
+            Some(_) => self.advance_traced(ip, optimize)?,
+        };
+        if let (Some(history), Some(undo)) = (&mut self.history, undo) {
+            history.record(undo);
         }
-        ip += 1;
 
-        if let Instruction::Jnz { valid: false, .. } = instruction {
-            return ip;
+        Ok(())
+    }
+
+    /// Like [`Computer::advance`], recording the step in the trace.
+    fn advance_traced(&mut self, ip: usize, optimize: bool) -> Result<usize, OverflowError> {
+        let before = self.registers.clone();
+        let instruction = self.instructions[ip].to_string();
+        let fused = self.idioms[ip].filter(|_| optimize).filter(|i| {
+            i.apply(&mut self.registers, self.architecture.word, ip)
+                .is_some()
+        });
+
+        let next_ip = match fused {
+            Some(idiom) => ip + idiom.len(),
+            None => self.execute(ip)?,
+        };
+
+        let instruction = fused.map_or(instruction, |i| i.to_string());
+        if let Some(trace) = &mut self.trace {
+            trace.record(ip, next_ip, instruction, &before, &self.registers);
         }
 
-        match instruction {
-            Instruction::Cpy { from, to, .. } => match from {
-                Value::R { register } => {
-                    *self.registers.get_mut(to).unwrap() = self.registers[register]
+        Ok(next_ip)
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.ip >= self.instructions.len()
+    }
+
+    /// Whether the instruction pointer is at an `in` while there is no input left.
+    pub fn is_waiting(&self) -> bool {
+        !self.is_halted() && self.input.is_empty() && matches!(self.ops[self.ip], Op::In { .. })
+    }
+
+    pub fn architecture(&self) -> Architecture {
+        self.architecture
+    }
+
+    /// The current instructions, one line each, including the effects of `tgl`.
+    pub fn listing(&self) -> Vec<String> {
+        self.instructions.iter().map(|i| i.to_string()).collect()
+    }
+
+    /// Executes the idiom (when optimizing) or else the instruction at `ip`, without tracing.
+    fn advance(&mut self, ip: usize, optimize: bool) -> Result<usize, OverflowError> {
+        if optimize {
+            if let Some(ip) = self.idioms[ip]
+                .as_ref()
+                .and_then(|i| i.apply(&mut self.registers, self.architecture.word, ip))
+            {
+                return Ok(ip);
+            }
+        }
+
+        self.execute(ip)
+    }
+
+    fn execute(&mut self, ip: usize) -> Result<usize, OverflowError> {
+        match self.ops[ip] {
+            Op::CopyRegister { from, to } => self.registers[&to] = self.registers[&from],
+            Op::CopyConstant { constant, to } => self.registers[&to] = constant,
+            Op::Add { register, amount } => {
+                self.registers[&register] = self
+                    .architecture
+                    .word
+                    .add(self.registers[&register], amount)
+                    .ok_or(OverflowError { ip })?
+            }
+            Op::JumpIfNotZero { register, offset } => {
+                if self.registers[&register] != 0 {
+                    return Ok(jump(ip, offset));
                 }
-                Value::C { constant } => *self.registers.get_mut(to).unwrap() = *constant,
-            },
-            Instruction::Inc { register, .. } => *self.registers.get_mut(register).unwrap() += 1,
-            Instruction::Dec { register, .. } => *self.registers.get_mut(register).unwrap() -= 1,
-            Instruction::Jnz { value, offset, .. } => {
-                let i_value = match value {
-                    Value::R { register } => self.registers[register],
-                    Value::C { constant } => *constant,
-                };
-                let offset_value = match offset {
-                    Value::R { register } => self.registers[register],
-                    Value::C { constant } => *constant,
-                };
-
-                if i_value != 0 {
-                    ip = (ip as i32 + offset_value) as usize - 1;
+            }
+            Op::Jump { offset } => return Ok(jump(ip, offset)),
+            Op::JumpDynamic { value, offset } => {
+                if self.value(value) != 0 {
+                    return Ok(jump(ip, self.value(offset)));
                 }
             }
-            Instruction::Tgl { register, .. } => {
-                let idx = ip as i32 - 1 + self.registers[register];
-
-                if idx >= 0 && idx < self.instructions.len() as i32 {
-                    let idx = idx as usize;
-                    self.instructions[idx] = self.instructions[idx].toggle();
+            Op::Toggle { register } => {
+                if let Some(idx) = self.toggle_target(ip, register) {
+                    self.replace(idx, self.instructions[idx].toggle());
                 }
             }
-            Instruction::Out { value } => {
-                let out_value = match value {
-                    Value::R { register } => self.registers[register],
-                    Value::C { constant } => *constant,
-                };
-                self.output.push(out_value);
+            Op::In { register } => match self.input.pop_front() {
+                Some(value) => self.registers[&register] = self.architecture.word.wrap(value),
+                None => return Ok(ip),
+            },
+            Op::Out { value } => {
+                let value = self.value(value);
+                self.output.push(value);
             }
+            Op::Skip => {}
+        }
+
+        Ok(ip + 1)
+    }
+
+    /// The instruction the `tgl` at `ip` toggles, `None` when it points outside the program.
+    fn toggle_target(&self, ip: usize, register: Register) -> Option<usize> {
+        (ip as i64)
+            .checked_add(self.registers[&register])
+            .and_then(|idx| usize::try_from(idx).ok())
+            .filter(|idx| *idx < self.instructions.len())
+    }
+
+    fn replace(&mut self, idx: usize, instruction: Instruction) {
+        self.instructions[idx] = instruction;
+        self.ops[idx] = Op::decode(&self.instructions[idx]);
+        optimizer::refresh(&mut self.idioms, &self.instructions, idx);
+    }
+
+    fn value(&self, value: Value) -> i64 {
+        match value {
+            Value::R { register } => self.registers[&register],
+            Value::C { constant } => constant,
         }
-        ip
     }
 }
 
+/// Iterator over the values output by a [`Computer`], see [`Computer::outputs`].
 #[derive(Debug, Clone)]
+pub struct Outputs {
+    computer: Computer,
+    optimize: bool,
+    overflowed: bool,
+}
+
+impl Outputs {
+    pub fn computer(&self) -> &Computer {
+        &self.computer
+    }
+}
+
+/// Ends after the program halted or started waiting for input, or right after yielding an overflow.
+impl Iterator for Outputs {
+    type Item = Result<i64, OverflowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let output_len = self.computer.output.len();
+
+        while !self.computer.is_halted() && !self.computer.is_waiting() && !self.overflowed {
+            if let Err(error) = self.computer.step(self.optimize) {
+                self.overflowed = true;
+                return Some(Err(error));
+            }
+
+            if self.computer.output.len() > output_len {
+                return self.computer.output.last().copied().map(Ok);
+            }
+        }
+
+        None
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ClockSignal {
+    /// Outputs `0, 1, 0, 1, ...` forever.
+    Proven,
+    /// Outputs something else, or halts.
+    Disproven,
+    /// Outputs a correct clock signal for as long as it was checked.
+    Undecided,
+}
+
+/// A value overflowed a [`Word::Checked`] word, while executing the instruction at `ip`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct OverflowError {
+    pub ip: usize,
+}
+
+impl Display for OverflowError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "overflow at instruction {}", self.ip)
+    }
+}
+
+impl Error for OverflowError {}
+
+/// Where a jump from `ip` lands, jumping before the first instruction halts the program.
+fn jump(ip: usize, offset: i64) -> usize {
+    (ip as i64)
+        .checked_add(offset)
+        .and_then(|ip| usize::try_from(ip).ok())
+        .unwrap_or(usize::MAX)
+}
+
+/// The machine a program runs on, the default being the standard `a` to `d` registers holding `i32`s.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Architecture {
+    pub registers: RegisterFile,
+    pub word: Word,
+}
+
+/// The registers of an [`Architecture`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RegisterFile {
+    /// `a`, `b`, `c`, ... (at most 26).
+    Letters(usize),
+    /// `r0`, `r1`, `r2`, ... (at most 256).
+    Numbered(usize),
+}
+
+impl RegisterFile {
+    pub fn len(&self) -> usize {
+        match *self {
+            RegisterFile::Letters(count) => count.min(26),
+            RegisterFile::Numbered(count) => count.min(256),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, register: Register) -> bool {
+        match (self, register) {
+            (RegisterFile::Letters(_), Register::Letter(_))
+            | (RegisterFile::Numbered(_), Register::Numbered(_)) => register.index() < self.len(),
+            _ => false,
+        }
+    }
+
+    /// All registers, in order.
+    pub fn registers(&self) -> Vec<Register> {
+        (0..self.len() as u8)
+            .map(|idx| match self {
+                RegisterFile::Letters(_) => Register::Letter(idx),
+                RegisterFile::Numbered(_) => Register::Numbered(idx),
+            })
+            .collect()
+    }
+}
+
+impl Default for RegisterFile {
+    fn default() -> Self {
+        RegisterFile::Letters(4)
+    }
+}
+
+/// How values are stored in registers.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub enum Word {
+    /// 32-bit values, wrapping around on overflow.
+    #[default]
+    I32,
+    /// 64-bit values, wrapping around on overflow.
+    I64,
+    /// 32-bit values, overflowing stops the program with an [`OverflowError`].
+    Checked,
+}
+
+impl Word {
+    pub fn min(&self) -> i64 {
+        match self {
+            Word::I32 | Word::Checked => i32::MIN as i64,
+            Word::I64 => i64::MIN,
+        }
+    }
+
+    pub fn max(&self) -> i64 {
+        match self {
+            Word::I32 | Word::Checked => i32::MAX as i64,
+            Word::I64 => i64::MAX,
+        }
+    }
+
+    /// Wraps the value around to fit the word, whether it is checked or not.
+    pub fn wrap(&self, value: i64) -> i64 {
+        match self {
+            Word::I32 | Word::Checked => value as i32 as i64,
+            Word::I64 => value,
+        }
+    }
+
+    /// `None` when the sum overflows a checked word.
+    fn add(&self, left: i64, right: i64) -> Option<i64> {
+        self.fit(left.checked_add(right), left.wrapping_add(right))
+    }
+
+    /// `None` when the product overflows a checked word.
+    fn mul(&self, left: i64, right: i64) -> Option<i64> {
+        self.fit(left.checked_mul(right), left.wrapping_mul(right))
+    }
+
+    fn fit(&self, exact: Option<i64>, wrapped: i64) -> Option<i64> {
+        match self {
+            Word::Checked => exact.filter(|v| (self.min()..=self.max()).contains(v)),
+            _ => Some(self.wrap(wrapped)),
+        }
+    }
+}
+
+/// The values of all registers, indexed by [`Register`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Registers {
+    file: RegisterFile,
+    values: Vec<i64>,
+}
+
+impl Registers {
+    /// All registers of the file, set to zero.
+    pub fn new(file: RegisterFile) -> Self {
+        Self {
+            file,
+            values: vec![0; file.len()],
+        }
+    }
+
+    pub fn file(&self) -> RegisterFile {
+        self.file
+    }
+
+    /// The values, in the order of [`RegisterFile::registers`].
+    pub fn values(&self) -> &[i64] {
+        &self.values
+    }
+}
+
+impl Default for Registers {
+    fn default() -> Self {
+        Self::new(RegisterFile::default())
+    }
+}
+
+impl Index<&Register> for Registers {
+    type Output = i64;
+
+    fn index(&self, register: &Register) -> &Self::Output {
+        &self.values[register.index()]
+    }
+}
+
+impl IndexMut<&Register> for Registers {
+    fn index_mut(&mut self, register: &Register) -> &mut Self::Output {
+        &mut self.values[register.index()]
+    }
+}
+
+/// An instruction decoded ahead of time into the cheapest operation with the same effect.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Op {
+    CopyRegister { from: Register, to: Register },
+    CopyConstant { constant: i64, to: Register },
+    Add { register: Register, amount: i64 },
+    JumpIfNotZero { register: Register, offset: i64 },
+    Jump { offset: i64 },
+    JumpDynamic { value: Value, offset: Value },
+    Toggle { register: Register },
+    In { register: Register },
+    Out { value: Value },
+    Skip,
+}
+
+impl Op {
+    fn decode(instruction: &Instruction) -> Self {
+        match *instruction {
+            Instruction::Cpy {
+                from: Value::R { register },
+                to,
+            } => Op::CopyRegister { from: register, to },
+            Instruction::Cpy {
+                from: Value::C { constant },
+                to,
+            } => Op::CopyConstant { constant, to },
+            Instruction::Inc { register } => Op::Add {
+                register,
+                amount: 1,
+            },
+            Instruction::Dec { register } => Op::Add {
+                register,
+                amount: -1,
+            },
+            Instruction::Jnz { valid: false, .. } => Op::Skip,
+            Instruction::Jnz {
+                value: Value::C { constant: 0 },
+                ..
+            } => Op::Skip,
+            Instruction::Jnz {
+                value: Value::C { .. },
+                offset: Value::C { constant },
+                ..
+            } => Op::Jump { offset: constant },
+            Instruction::Jnz {
+                value: Value::R { register },
+                offset: Value::C { constant },
+                ..
+            } => Op::JumpIfNotZero {
+                register,
+                offset: constant,
+            },
+            Instruction::Jnz { value, offset, .. } => Op::JumpDynamic { value, offset },
+            Instruction::Tgl { register } => Op::Toggle { register },
+            Instruction::In { register } => Op::In { register },
+            Instruction::Out { value } => Op::Out { value },
+            Instruction::InvalidStep { .. } => Op::Skip,
+        }
+    }
+}
+
+/// A parsed assembunny program, see [`parse_program`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Program {
+    instructions: Vec<Instruction>,
+    architecture: Architecture,
+}
+
+impl Program {
+    pub fn architecture(&self) -> Architecture {
+        self.architecture
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 enum Instruction {
     Cpy {
         from: Value,
@@ -126,42 +622,26 @@ enum Instruction {
     Tgl {
         register: Register,
     },
+    In {
+        register: Register,
+    },
     Out {
         value: Value,
     },
+    /// An `inc` (`step` 1) or `dec` (`step` -1) of a constant, made by toggling an `out` of a constant.
+    /// Like a `cpy` to a constant it is invalid, and gets skipped.
+    InvalidStep {
+        constant: i64,
+        step: i64,
+    },
 }
 
 impl Instruction {
-    fn new(ins: &str) -> Self {
-        match &ins[0..3] {
-            "cpy" => Instruction::Cpy {
-                from: Value::new(&ins[4..ins.len() - 2]),
-                to: Register::new(&ins[ins.len() - 1..]),
-            },
-            "inc" => Instruction::Inc {
-                register: Register::new(&ins[4..]),
-            },
-            "dec" => Instruction::Dec {
-                register: Register::new(&ins[4..]),
-            },
-            "jnz" => {
-                let split = &ins[4..].split(' ').collect_vec();
-                Instruction::Jnz {
-                    value: Value::new(split[0]),
-                    offset: Value::new(split[1]),
-                    valid: true,
-                }
-            }
-            "tgl" => Instruction::Tgl {
-                register: Register::new(&ins[4..]),
-            },
-            "out" => Instruction::Out {
-                value: Value::new(&ins[4..]),
-            },
-            _ => unreachable!(),
-        }
-    }
-
+    /// The instruction `tgl` turns this one into.
+    ///
+    /// One-argument instructions become `dec` when they are an `inc`, and `inc` otherwise (`out` included).
+    /// Two-argument instructions become `cpy` when they are a `jnz`, and `jnz` otherwise. Instructions
+    /// this turns invalid are kept (and skipped) so that toggling them again makes them valid again.
     fn toggle(&self) -> Self {
         match self.clone() {
             Instruction::Cpy { from, to } => Instruction::Jnz {
@@ -196,8 +676,45 @@ impl Instruction {
                     }
                 }
             }
-            Instruction::Tgl { register } => Instruction::Inc { register },
-            Instruction::Out { .. } => unimplemented!(),
+            Instruction::Tgl { register } | Instruction::In { register } => {
+                Instruction::Inc { register }
+            }
+            Instruction::Out {
+                value: Value::R { register },
+            } => Instruction::Inc { register },
+            Instruction::Out {
+                value: Value::C { constant },
+            } => Instruction::InvalidStep { constant, step: 1 },
+            Instruction::InvalidStep { constant, step } => Instruction::InvalidStep {
+                constant,
+                step: if step == 1 { -1 } else { 1 },
+            },
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Cpy { from, to } => write!(f, "cpy {} {}", from, to),
+            Instruction::Inc { register } => write!(f, "inc {}", register),
+            Instruction::Dec { register } => write!(f, "dec {}", register),
+            Instruction::Jnz {
+                value,
+                offset,
+                valid: true,
+            } => write!(f, "jnz {} {}", value, offset),
+            // A toggled `jnz` with a constant offset, it would be an invalid `cpy` that gets skipped
+            Instruction::Jnz {
+                value,
+                offset,
+                valid: false,
+            } => write!(f, "cpy {} {}", value, offset),
+            Instruction::Tgl { register } => write!(f, "tgl {}", register),
+            Instruction::In { register } => write!(f, "in {}", register),
+            Instruction::Out { value } => write!(f, "out {}", value),
+            Instruction::InvalidStep { constant, step: 1 } => write!(f, "inc {}", constant),
+            Instruction::InvalidStep { constant, .. } => write!(f, "dec {}", constant),
         }
     }
 }
@@ -205,38 +722,258 @@ impl Instruction {
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 enum Value {
     R { register: Register },
-    C { constant: i32 },
+    C { constant: i64 },
 }
 
-impl Value {
-    fn new(value: &str) -> Self {
-        match value {
-            "a" | "b" | "c" | "d" => Self::R {
-                register: Register::new(value),
-            },
-            _ => Self::C {
-                constant: i32::from_str(value).unwrap(),
-            },
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::R { register } => write!(f, "{}", register),
+            Value::C { constant } => write!(f, "{}", constant),
         }
     }
 }
 
+/// A register, named either by a lowercase letter or by its number.
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum Register {
-    A,
-    B,
-    C,
-    D,
+    /// `a`, `b`, `c`, ...
+    Letter(u8),
+    /// `r0`, `r1`, `r2`, ...
+    Numbered(u8),
 }
 
 impl Register {
-    fn new(register: &str) -> Self {
-        match register {
-            "a" => Register::A,
-            "b" => Register::B,
-            "c" => Register::C,
-            "d" => Register::D,
-            _ => unreachable!(),
+    pub const A: Register = Register::Letter(0);
+    pub const B: Register = Register::Letter(1);
+    pub const C: Register = Register::Letter(2);
+    pub const D: Register = Register::Letter(3);
+
+    /// Any register name, whether or not it is part of a given [`RegisterFile`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        if let [letter @ b'a'..=b'z'] = name.as_bytes() {
+            return Some(Register::Letter(letter - b'a'));
+        }
+
+        let number = name.strip_prefix('r')?;
+        let canonical = number.starts_with('0').not() || number == "0";
+        let digits = number.bytes().all(|b| b.is_ascii_digit());
+        (canonical && digits)
+            .then(|| u8::from_str(number).ok())
+            .flatten()
+            .map(Register::Numbered)
+    }
+
+    /// Index in the [`RegisterFile`].
+    pub fn index(&self) -> usize {
+        match *self {
+            Register::Letter(idx) | Register::Numbered(idx) => idx as usize,
+        }
+    }
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Register::Letter(idx) => write!(f, "{}", (b'a' + idx) as char),
+            Register::Numbered(idx) => write!(f, "r{}", idx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn streams_outputs() {
+        let computer = Computer::new(&["cpy 3 a", "out a", "dec a", "jnz a -2"], vec![]).unwrap();
+
+        assert_eq!(
+            vec![Ok(3), Ok(2), Ok(1)],
+            computer.outputs(true).collect_vec()
+        );
+    }
+
+    #[test]
+    fn reads_input_and_waits_for_more() {
+        let computer = Computer::new(&["in a", "out a", "jnz 1 -2"], vec![])
+            .unwrap()
+            .with_input([7, 8]);
+
+        let mut computer = computer.run(true, 10).unwrap();
+        assert_eq!(vec![7, 8], computer.output);
+        assert!(computer.is_waiting());
+        assert_eq!(0, computer.ip());
+
+        computer.input.push_back(9);
+        assert_eq!(vec![Ok(9)], computer.outputs(false).collect_vec());
+    }
+
+    #[test]
+    fn toggles_every_instruction() {
+        // The instruction, toggled once and twice, and whether the first toggle makes it invalid
+        let toggles = [
+            ("cpy a b", "jnz a b", "cpy a b", false),
+            ("cpy 1 b", "jnz 1 b", "cpy 1 b", false),
+            ("inc a", "dec a", "inc a", false),
+            ("dec a", "inc a", "dec a", false),
+            ("jnz a b", "cpy a b", "jnz a b", false),
+            ("jnz a 2", "cpy a 2", "jnz a 2", true),
+            ("tgl a", "inc a", "dec a", false),
+            ("in a", "inc a", "dec a", false),
+            ("out a", "inc a", "dec a", false),
+            ("out 3", "inc 3", "dec 3", true),
+        ];
+
+        for (source, once, twice, invalid) in toggles {
+            let instruction = parse_program(source).unwrap().instructions.remove(0);
+            let toggled = instruction.toggle();
+
+            assert_eq!(once, toggled.to_string(), "{}", source);
+            assert_eq!(twice, toggled.toggle().to_string(), "{}", source);
+            assert_eq!(invalid, Op::decode(&toggled) == Op::Skip, "{}", source);
         }
+        assert_eq!(
+            Instruction::InvalidStep {
+                constant: 3,
+                step: 1
+            },
+            Instruction::InvalidStep {
+                constant: 3,
+                step: -1
+            }
+            .toggle()
+        );
+    }
+
+    #[test]
+    fn toggles_outputs() {
+        // Toggles the `out a` into `inc a`, the `out 7` into a skipped `inc 7`, and tries toggling
+        // instructions before and after the program
+        let code = [
+            "cpy 7 a", "tgl a", "cpy 7 b", "tgl b", "cpy -9 c", "tgl c", "cpy 9 c", "tgl c",
+            "out a", "out a", "out 7", "out a",
+        ];
+        let computer = Computer::new(&code, vec![])
+            .unwrap()
+            .run(false, 10)
+            .unwrap();
+
+        assert_eq!(vec![8, 8], computer.output);
+        assert_eq!("inc a", computer.listing()[8]);
+        assert_eq!("inc 7", computer.listing()[10]);
+    }
+
+    #[test]
+    fn checks_clock_signal() {
+        let clock = ["out 0", "cpy 1 a", "out a", "jnz 1 -3"];
+        let wrong = ["out 0", "out 1", "out 1"];
+        let halting = ["out 0", "out 1"];
+        let diverging = ["out 0", "out 1", "inc b", "jnz 1 -3"];
+        let stuck = ["out 0", "out 1", "jnz 1 0"];
+
+        let check = |code: &[&str]| {
+            Computer::new(code, vec![])
+                .unwrap()
+                .check_clock_signal(true, 100)
+        };
+
+        assert_eq!(ClockSignal::Proven, check(&clock));
+        assert_eq!(ClockSignal::Disproven, check(&wrong));
+        assert_eq!(ClockSignal::Disproven, check(&halting));
+        assert_eq!(ClockSignal::Undecided, check(&diverging));
+        assert_eq!(ClockSignal::Disproven, check(&stuck));
+    }
+
+    #[test]
+    fn runs_on_other_register_files() {
+        let letters = Architecture {
+            registers: RegisterFile::Letters(6),
+            word: Word::I32,
+        };
+        let numbered = Architecture {
+            registers: RegisterFile::Numbered(12),
+            word: Word::I32,
+        };
+        let run = |source: &str, architecture, overrides| {
+            let program = parse_program_for(source, architecture).unwrap();
+            Computer::from_program(program, overrides)
+                .run(true, 0)
+                .unwrap()
+                .registers
+        };
+
+        let registers = run("cpy 3 f\ncpy f e\ninc a\ndec f\njnz f -2", letters, vec![]);
+        assert_eq!(&[3, 0, 0, 0, 3, 0], registers.values());
+
+        let registers = run(
+            "cpy r11 r10\ninc r0\ndec r10\njnz r10 -2",
+            numbered,
+            vec![(Register::Numbered(11), 4)],
+        );
+        assert_eq!(4, registers[&Register::Numbered(0)]);
+        assert_eq!(4, registers[&Register::Numbered(11)]);
+        assert_eq!(
+            vec!["r0", "r1", "r2"],
+            RegisterFile::Numbered(3)
+                .registers()
+                .iter()
+                .map(|r| r.to_string())
+                .collect_vec()
+        );
+    }
+
+    #[test]
+    fn parses_register_names() {
+        assert_eq!(Some(Register::Letter(4)), Register::from_name("e"));
+        assert_eq!(Some(Register::Numbered(0)), Register::from_name("r0"));
+        assert_eq!(Some(Register::Numbered(255)), Register::from_name("r255"));
+        assert_eq!(Some(Register::Letter(17)), Register::from_name("r"));
+        assert_eq!(None, Register::from_name("r256"));
+        assert_eq!(None, Register::from_name("r01"));
+        assert_eq!(None, Register::from_name("A"));
+        assert!(RegisterFile::Letters(4).contains(Register::D));
+        assert!(!RegisterFile::Letters(4).contains(Register::Letter(4)));
+        assert!(!RegisterFile::Numbered(4).contains(Register::A));
+    }
+
+    #[test]
+    fn wraps_or_reports_overflow() {
+        let increment = "cpy 2147483647 a\ninc a";
+        // a += 100 * 100, with `a` starting close to the largest `i32`
+        let multiply = "cpy 100 b\ncpy 100 d\ncpy b c\ninc a\ndec c\njnz c -2\ndec d\njnz d -5";
+
+        let run = |source: &str, word, optimize| {
+            let architecture = Architecture {
+                registers: RegisterFile::default(),
+                word,
+            };
+            let program = parse_program_for(source, architecture).unwrap();
+            Computer::from_program(program, vec![(Register::A, 2_147_483_000)])
+                .run(optimize, 0)
+                .map(|c| c.registers[&Register::A])
+        };
+
+        assert_eq!(Ok(-2_147_483_648), run(increment, Word::I32, false));
+        assert_eq!(Ok(2_147_483_648), run(increment, Word::I64, false));
+        assert_eq!(
+            Err(OverflowError { ip: 1 }),
+            run(increment, Word::Checked, false)
+        );
+
+        assert_eq!(Ok(-2_147_474_296), run(multiply, Word::I32, true));
+        assert_eq!(
+            run(multiply, Word::I32, false),
+            run(multiply, Word::I32, true)
+        );
+        assert_eq!(Ok(2_147_493_000), run(multiply, Word::I64, true));
+        // The fused multiplication overflows, stepping through it finds the `inc` that does
+        assert_eq!(
+            Err(OverflowError { ip: 3 }),
+            run(multiply, Word::Checked, true)
+        );
     }
 }
@@ -0,0 +1,356 @@
+use std::ops::Not;
+use std::str::FromStr;
+
+use rustc_hash::FxHashMap;
+
+use super::parser::{
+    self, Statement, Token, Tokens, ARGUMENT, CLOSED_MACRO, END_OF_LINE, GLOBAL_SCOPE, INTEGER,
+    MNEMONICS, NAME, NEW_NAME, NON_RECURSIVE_MACRO,
+};
+use super::{Architecture, ParseError, Program, RegisterFile};
+
+const CONST: &str = "const";
+const MACRO: &str = "macro";
+const END: &str = "end";
+const MUL: &str = "mul";
+/// Registers `mul` can take as scratch register when it's left out.
+const SCRATCH_REGISTERS: [&str; 4] = ["a", "b", "c", "d"];
+/// Expected when all of [`SCRATCH_REGISTERS`] are operands of `mul`.
+const FREE_REGISTER: &str = "operands leaving a register free for scratch";
+
+/// Macros available to every assembled program.
+const PRELUDE: &str = "\
+# target += counter, leaves counter at 0
+macro add target counter
+    inc target
+    dec counter
+    jnz counter -2
+end
+
+# target += source * counter, leaves counter and scratch at 0
+macro mul target source counter scratch
+    cpy source scratch
+    add target scratch
+    dec counter
+    jnz counter -5
+end
+";
+
+/// Assembles source code into a program, on top of everything [`super::parse_program`] accepts:
+///
+/// * `const NAME 42` defines a symbolic constant, usable from then on wherever a value is expected.
+/// * `macro NAME param...` up to a line with `end` defines a macro, each call `NAME arg...` is replaced
+///   by the macro body with the parameters replaced by the arguments.
+///   Labels defined in the body are local to each call.
+/// * The macros `add target counter` and `mul target source counter [scratch]` are always available.
+///   `mul a b d` is the multiplication loop of day 23, `a += b * d`, keeping `b` intact by copying it
+///   into a scratch register each round. Left out, the scratch register is the first of `a` to `d`
+///   not among the operands, here `c`. Like the counter it's left at 0, so it can't hold a value
+///   needed after the call: name another one explicitly then, as in `mul a b d c`.
+pub fn assemble(source: &str) -> Result<Program, ParseError> {
+    let mut assembler = Assembler::default();
+
+    assembler.read(PRELUDE)?;
+    assembler.statements.clear();
+    assembler.read(source)?;
+
+    parser::parse_statements(assembler.statements, Architecture::default())
+}
+
+#[derive(Debug, Clone)]
+struct Macro<'a> {
+    params: Vec<&'a str>,
+    body: Vec<Tokens<'a>>,
+}
+
+#[derive(Default)]
+struct Assembler<'a> {
+    constants: FxHashMap<&'a str, Token<'a>>,
+    macros: FxHashMap<&'a str, Macro<'a>>,
+    statements: Vec<Statement<'a>>,
+    scopes: usize,
+    expanding: Vec<&'a str>,
+}
+
+impl<'a> Assembler<'a> {
+    fn read(&mut self, source: &'a str) -> Result<(), ParseError> {
+        let mut lines = parser::tokenize(source).into_iter();
+
+        while let Some(tokens) = lines.next() {
+            match tokens.tokens.first().map(|t| t.text) {
+                Some(CONST) => self.define_constant(tokens)?,
+                Some(MACRO) => self.define_macro(tokens, &mut lines)?,
+                _ => self.emit(tokens, GLOBAL_SCOPE)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn define_constant(&mut self, tokens: Tokens<'a>) -> Result<(), ParseError> {
+        let name = self.new_name(tokens.tokens.get(1).copied().unwrap_or(tokens.end))?;
+
+        let value = tokens.tokens.get(2).copied().unwrap_or(tokens.end);
+        if i32::from_str(value.text).is_err() {
+            return Err(value.unexpected(&[INTEGER]));
+        }
+        if let Some(extra) = tokens.tokens.get(3) {
+            return Err(extra.unexpected(&[END_OF_LINE]));
+        }
+
+        self.constants.insert(name, value);
+        Ok(())
+    }
+
+    fn define_macro(
+        &mut self,
+        tokens: Tokens<'a>,
+        lines: &mut impl Iterator<Item = Tokens<'a>>,
+    ) -> Result<(), ParseError> {
+        let name = self.new_name(tokens.tokens.get(1).copied().unwrap_or(tokens.end))?;
+
+        let mut params: Vec<&str> = vec![];
+        for param in tokens.tokens.iter().skip(2) {
+            if is_name(param.text).not() {
+                return Err(param.unexpected(&[NAME]));
+            }
+            if params.contains(&param.text) {
+                return Err(param.unexpected(&[NEW_NAME]));
+            }
+            params.push(param.text);
+        }
+
+        let mut body = vec![];
+        loop {
+            match lines.next() {
+                None => return Err(tokens.tokens[0].unexpected(&[CLOSED_MACRO])),
+                Some(line) if line.tokens.first().map(|t| t.text) == Some(END) => {
+                    if let Some(extra) = line.tokens.get(1) {
+                        return Err(extra.unexpected(&[END_OF_LINE]));
+                    }
+                    break;
+                }
+                Some(line) => body.push(line),
+            }
+        }
+
+        self.macros.insert(name, Macro { params, body });
+        Ok(())
+    }
+
+    /// Checks the name for a new constant or macro.
+    fn new_name(&self, token: Token<'a>) -> Result<&'a str, ParseError> {
+        if is_name(token.text).not() {
+            return Err(token.unexpected(&[NAME]));
+        }
+        if self.constants.contains_key(token.text) || self.macros.contains_key(token.text) {
+            return Err(token.unexpected(&[NEW_NAME]));
+        }
+
+        Ok(token.text)
+    }
+
+    /// Adds the statement(s) for a line of code, after replacing constants and expanding macros.
+    fn emit(&mut self, tokens: Tokens<'a>, scope: usize) -> Result<(), ParseError> {
+        let end = tokens.end;
+        let mut tokens = tokens.tokens.into_iter().peekable();
+
+        if let Some(label) = tokens.next_if(|t| t.text.ends_with(':')) {
+            self.statements.push(Statement {
+                scope,
+                tokens: Tokens::from_parts(vec![label], end),
+            });
+        }
+
+        let Some(mnemonic) = tokens.next() else {
+            return Ok(());
+        };
+        let mut operands = tokens
+            .map(|t| match self.constants.get(t.text) {
+                Some(constant) => Token {
+                    text: constant.text,
+                    ..t
+                },
+                None => t,
+            })
+            .collect::<Vec<_>>();
+        if mnemonic.text == MUL && operands.len() == 3 {
+            operands.push(Token {
+                text: scratch_register(&operands)
+                    .ok_or_else(|| end.unexpected(&[FREE_REGISTER]))?,
+                ..end
+            });
+        }
+
+        match self.macros.get(mnemonic.text).cloned() {
+            Some(definition) => self.expand(mnemonic, definition, operands, end),
+            None => {
+                self.statements.push(Statement {
+                    scope,
+                    tokens: Tokens::from_parts([vec![mnemonic], operands].concat(), end),
+                });
+                Ok(())
+            }
+        }
+    }
+
+    fn expand(
+        &mut self,
+        name: Token<'a>,
+        definition: Macro<'a>,
+        arguments: Vec<Token<'a>>,
+        end: Token<'a>,
+    ) -> Result<(), ParseError> {
+        if self.expanding.contains(&name.text) {
+            return Err(name.unexpected(&[NON_RECURSIVE_MACRO]));
+        }
+        if arguments.len() < definition.params.len() {
+            return Err(end.unexpected(&[ARGUMENT]));
+        }
+        if let Some(extra) = arguments.get(definition.params.len()) {
+            return Err(extra.unexpected(&[END_OF_LINE]));
+        }
+
+        self.scopes += 1;
+        let scope = self.scopes;
+        self.expanding.push(name.text);
+
+        for line in definition.body {
+            let tokens = line
+                .tokens
+                .iter()
+                .map(
+                    |t| match definition.params.iter().position(|p| *p == t.text) {
+                        Some(idx) => arguments[idx],
+                        None => *t,
+                    },
+                )
+                .collect();
+
+            self.emit(Tokens::from_parts(tokens, line.end), scope)?;
+        }
+
+        self.expanding.pop();
+        Ok(())
+    }
+}
+
+/// The first register `mul` can take as scratch register, not being one of its operands.
+fn scratch_register(operands: &[Token]) -> Option<&'static str> {
+    SCRATCH_REGISTERS
+        .into_iter()
+        .find(|register| operands.iter().all(|o| o.text != *register))
+}
+
+/// Names of constants, macros and macro parameters follow the label rules, but can't be keywords.
+fn is_name(name: &str) -> bool {
+    let keyword = [CONST, MACRO, END].contains(&name) || MNEMONICS.contains(&name);
+
+    parser::is_label(name, RegisterFile::default()) && keyword.not()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{parse_program, Computer, Register};
+    use super::*;
+
+    fn error(line: usize, column: usize, token: &str, expected: &[&str]) -> ParseError {
+        ParseError {
+            line,
+            column,
+            token: token.to_string(),
+            expected: expected.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn replaces_constants() {
+        let source = "const START 41\nconst BACK -2\ncpy START a\ninc a\njnz 0 BACK";
+
+        assert_eq!(parse_program("cpy 41 a\ninc a\njnz 0 -2"), assemble(source));
+    }
+
+    #[test]
+    fn expands_prelude_macros() {
+        let program = assemble("cpy 6 b\ncpy 7 d\nmul a b d c\nadd a b").unwrap();
+
+        assert_eq!(
+            parse_program(
+                "cpy 6 b\ncpy 7 d\ncpy b c\ninc a\ndec c\njnz c -2\ndec d\njnz d -5\n\
+                 inc a\ndec b\njnz b -2"
+            ),
+            Ok(program.clone())
+        );
+        assert_eq!(
+            48,
+            Computer::from_program(program, vec![])
+                .run(false, 0)
+                .unwrap()
+                .registers[&Register::A]
+        );
+    }
+
+    #[test]
+    fn takes_a_free_scratch_register() {
+        let day_23 = parse_program("cpy b c\ninc a\ndec c\njnz c -2\ndec d\njnz d -5");
+
+        assert_eq!(day_23, assemble("mul a b d"));
+        assert_eq!(day_23, assemble("mul a b d c"));
+        assert_eq!(
+            parse_program("cpy 3 a\ninc d\ndec a\njnz a -2\ndec c\njnz c -5"),
+            assemble("const N 3\nmul d N c")
+        );
+    }
+
+    #[test]
+    fn expands_user_macros_with_local_labels() {
+        let source = "\
+macro countdown counter
+top: out counter
+    dec counter
+    jnz counter top
+end
+
+    cpy 2 a
+start: countdown a
+    cpy 3 b
+    countdown b
+    jnz 0 start
+";
+
+        assert_eq!(
+            parse_program(
+                "cpy 2 a\nout a\ndec a\njnz a -2\ncpy 3 b\nout b\ndec b\njnz b -2\njnz 0 -7"
+            ),
+            assemble(source)
+        );
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(Err(error(1, 7, "inc", &[NAME])), assemble("const inc 4"));
+        assert_eq!(
+            Err(error(2, 7, "N", &[NEW_NAME])),
+            assemble("const N 4\nconst N 5")
+        );
+        assert_eq!(Err(error(1, 9, "x", &[INTEGER])), assemble("const N x"));
+        assert_eq!(
+            Err(error(1, 1, "macro", &[CLOSED_MACRO])),
+            assemble("macro twice r\n  inc r\n  inc r")
+        );
+        assert_eq!(Err(error(1, 8, "", &[ARGUMENT])), assemble("mul a b"));
+        assert_eq!(Err(error(1, 9, "d", &[END_OF_LINE])), assemble("add a b d"));
+        assert_eq!(
+            Err(error(2, 5, "loop", &[NON_RECURSIVE_MACRO])),
+            assemble("macro loop\n    loop\nend\nloop")
+        );
+        assert_eq!(
+            Err(error(1, 14, "out", &[NAME])),
+            assemble("macro show r out\nend")
+        );
+        assert_eq!(
+            Err(error(1, 7, "5", &["a", "b", "c", "d"])),
+            assemble("add a 5")
+        );
+    }
+}
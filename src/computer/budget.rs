@@ -0,0 +1,216 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use super::{Computer, Instruction, OverflowError, Registers};
+
+/// The clock is only read every this many steps, to keep timing cheap.
+const CLOCK_INTERVAL: usize = 1 << 12;
+
+/// How far [`Computer::run_with`] may go, all limits are off by default.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct RunConfig {
+    /// Execute recognised loop idioms as single fused operations, see [`Computer::run`].
+    pub optimize: bool,
+    /// Stop once more than this many values were output.
+    pub output_len: Option<usize>,
+    /// Stop after this many steps, a fused loop idiom counting as a single step.
+    pub max_steps: Option<usize>,
+    /// Stop after running this long.
+    pub timeout: Option<Duration>,
+    /// Stop once the machine is back in a state it was in before, so it would never halt.
+    ///
+    /// With an [`RunConfig::output_len`], only once it didn't output anything since that state.
+    pub detect_loops: bool,
+}
+
+impl RunConfig {
+    pub fn optimized() -> Self {
+        Self {
+            optimize: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn output_len(mut self, output_len: usize) -> Self {
+        self.output_len = Some(output_len);
+        self
+    }
+
+    pub fn max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn detect_loops(mut self) -> Self {
+        self.detect_loops = true;
+        self
+    }
+}
+
+/// Why [`Computer::run_with`] stopped.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Outcome {
+    /// The instruction pointer left the program.
+    Halted,
+    /// The program is at an `in`, without any input left.
+    Waiting,
+    /// More values than [`RunConfig::output_len`] were output.
+    OutputLimit,
+    /// [`RunConfig::max_steps`] steps were executed.
+    StepLimit,
+    /// The program ran for longer than [`RunConfig::timeout`].
+    TimeLimit,
+    /// The program reached a state it was in before, it would keep repeating what it did since forever.
+    LoopDetected,
+}
+
+/// The result of [`Computer::run_with`]: the machine in its final state and why it stopped.
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub computer: Computer,
+    pub outcome: Outcome,
+    pub steps: usize,
+}
+
+/// A state of the machine, taken right after a backward jump, with the output length at that time.
+type State = (usize, Registers, Vec<Instruction>, VecDeque<i64>, usize);
+
+impl Computer {
+    /// Runs the program until it halts, waits for input or runs into one of the limits of the config.
+    ///
+    /// Loops are detected with Brent's algorithm, comparing the state after backward jumps with a single
+    /// saved state. It takes at most a few times the length of the loop to notice it, in constant memory.
+    ///
+    /// Fails when a value overflows a [`super::Word::Checked`] word.
+    pub fn run_with(mut self, config: RunConfig) -> Result<Run, OverflowError> {
+        let start = Instant::now();
+        let mut steps = 0;
+        let mut saved: Option<State> = None;
+        let (mut power, mut distance) = (1, 0);
+
+        let outcome = loop {
+            if self.is_halted() {
+                break Outcome::Halted;
+            }
+            if self.is_waiting() {
+                break Outcome::Waiting;
+            }
+            if config.output_len.is_some_and(|len| self.output.len() > len) {
+                break Outcome::OutputLimit;
+            }
+            if config.max_steps.is_some_and(|max| steps >= max) {
+                break Outcome::StepLimit;
+            }
+            if steps % CLOCK_INTERVAL == 0 && config.timeout.is_some_and(|t| start.elapsed() > t) {
+                break Outcome::TimeLimit;
+            }
+
+            let ip = self.ip;
+            self.step(config.optimize)?;
+            steps += 1;
+
+            if config.detect_loops && self.ip <= ip {
+                let silent =
+                    |state: &State| config.output_len.is_none() || self.output.len() == state.4;
+                if saved
+                    .as_ref()
+                    .is_some_and(|state| self.is_in(state) && silent(state))
+                {
+                    break Outcome::LoopDetected;
+                }
+
+                distance += 1;
+                if distance == power || saved.is_none() {
+                    saved = Some((
+                        self.ip,
+                        self.registers.clone(),
+                        self.instructions.clone(),
+                        self.input.clone(),
+                        self.output.len(),
+                    ));
+                    power *= 2;
+                    distance = 0;
+                }
+            }
+        };
+
+        Ok(Run {
+            computer: self,
+            outcome,
+            steps,
+        })
+    }
+
+    /// Whether the machine is in the state, comparing the cheap parts first.
+    fn is_in(&self, (ip, registers, instructions, input, _): &State) -> bool {
+        self.ip == *ip
+            && self.registers == *registers
+            && self.input == *input
+            && self.instructions == *instructions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(code: &[&str], config: RunConfig) -> (Outcome, usize) {
+        let run = Computer::new(code, vec![])
+            .unwrap()
+            .run_with(config)
+            .unwrap();
+        (run.outcome, run.steps)
+    }
+
+    #[test]
+    fn stops_at_the_first_limit() {
+        let counting = ["cpy 10 a", "dec a", "jnz a -1"];
+        let forever = ["inc a", "out a", "jnz 1 -2"];
+
+        assert_eq!((Outcome::Halted, 21), run(&counting, RunConfig::default()));
+        assert_eq!(
+            (Outcome::StepLimit, 5),
+            run(&counting, RunConfig::default().max_steps(5))
+        );
+        assert_eq!(
+            (Outcome::OutputLimit, 8),
+            run(&forever, RunConfig::optimized().output_len(2))
+        );
+        assert_eq!(
+            (Outcome::Waiting, 1),
+            run(&["inc a", "in b"], RunConfig::default().detect_loops())
+        );
+
+        let timeout = RunConfig::default().timeout(Duration::from_millis(10));
+        assert_eq!(Outcome::TimeLimit, run(&["inc a", "jnz 1 -1"], timeout).0);
+    }
+
+    #[test]
+    fn detects_loops() {
+        let config = RunConfig::optimized().detect_loops();
+        // Toggles the `jnz` back and forth, only repeating every second iteration
+        let toggling = ["cpy 2 b", "tgl b", "jnz 1 -1", "jnz 1 -2"];
+        let counting = ["cpy 1000 a", "dec a", "jnz a -1"];
+
+        assert_eq!(Outcome::LoopDetected, run(&["jnz 1 0"], config).0);
+        assert_eq!(
+            Outcome::LoopDetected,
+            run(&["inc a", "dec a", "jnz 1 -2"], config).0
+        );
+        assert_eq!(Outcome::LoopDetected, run(&toggling, config).0);
+        assert_eq!((Outcome::Halted, 2001), run(&counting, config));
+
+        // Outputs forever, which only counts as a loop when not limiting the output
+        let output = ["out 1", "jnz 1 -1"];
+        assert_eq!(Outcome::LoopDetected, run(&output, config).0);
+        assert_eq!(
+            (Outcome::OutputLimit, 199),
+            run(&output, config.output_len(99))
+        );
+    }
+}
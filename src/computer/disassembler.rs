@@ -0,0 +1,192 @@
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+use super::{optimizer, Instruction, Program, Value};
+
+/// Readable listing of the program, which parses back into the identical program.
+///
+/// Relative `jnz` offsets landing inside the program (or right after it) are replaced by labels,
+/// basic blocks are separated by blank lines and recognised loop idioms get a pseudo-code comment.
+impl Display for Program {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let targets = self.jump_targets();
+        let leaders = self.leaders(&targets);
+        let idioms = optimizer::detect(&self.instructions);
+        let mut annotated_until = 0;
+
+        for (idx, instruction) in self.instructions.iter().enumerate() {
+            if idx > 0 && leaders.contains(&idx) {
+                writeln!(f)?;
+            }
+            if targets.contains(&idx) {
+                writeln!(f, "{}:", label(idx))?;
+            }
+            if let Some(idiom) = idioms[idx].filter(|_| idx >= annotated_until) {
+                writeln!(f, "    # {}", idiom)?;
+                annotated_until = idx + idiom.len();
+            }
+
+            match self.jump_target(idx) {
+                Some(target) if targets.contains(&target) => {
+                    let Instruction::Jnz { value, .. } = instruction else {
+                        unreachable!()
+                    };
+                    writeln!(f, "    jnz {} {}", value, label(target))?;
+                }
+                _ => writeln!(f, "    {}", instruction)?,
+            }
+        }
+
+        let end = self.instructions.len();
+        if targets.contains(&end) {
+            writeln!(f)?;
+            writeln!(f, "{}:", label(end))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Program {
+    /// Where the `jnz` at `idx` jumps to, when its offset is a constant.
+    fn jump_target(&self, idx: usize) -> Option<usize> {
+        match self.instructions[idx] {
+            Instruction::Jnz {
+                offset: Value::C { constant },
+                valid: true,
+                ..
+            } => (idx as i64)
+                .checked_add(constant)
+                .and_then(|target| usize::try_from(target).ok()),
+            _ => None,
+        }
+    }
+
+    fn jump_targets(&self) -> BTreeSet<usize> {
+        (0..self.instructions.len())
+            .filter_map(|idx| self.jump_target(idx))
+            .filter(|target| *target <= self.instructions.len())
+            .collect()
+    }
+
+    /// Instructions starting a basic block: jump targets and instructions following a jump or a `tgl`.
+    fn leaders(&self, targets: &BTreeSet<usize>) -> BTreeSet<usize> {
+        self.instructions
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| matches!(i, Instruction::Jnz { .. } | Instruction::Tgl { .. }))
+            .map(|(idx, _)| idx + 1)
+            .chain(targets.iter().copied())
+            .collect()
+    }
+}
+
+fn label(idx: usize) -> String {
+    format!("L{}", idx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse_program;
+
+    const DAY_23: &str = "\
+cpy a b
+dec b
+cpy a d
+cpy 0 a
+cpy b c
+inc a
+dec c
+jnz c -2
+dec d
+jnz d -5
+dec b
+cpy b c
+cpy c d
+dec d
+inc c
+jnz d -2
+tgl c
+cpy -16 c
+jnz 1 c
+cpy 89 c
+jnz 77 d
+inc a
+inc d
+jnz d -2
+inc c
+jnz c -5
+";
+
+    #[test]
+    fn disassembles_with_labels_blocks_and_idioms() {
+        let program = parse_program(DAY_23).unwrap();
+
+        assert_eq!(
+            "    cpy a b
+    dec b
+    cpy a d
+    cpy 0 a
+
+L4:
+    # a += b * d
+    cpy b c
+
+L5:
+    inc a
+    dec c
+    jnz c L5
+
+    dec d
+    jnz d L4
+
+    dec b
+    cpy b c
+    cpy c d
+
+L13:
+    # c += d
+    dec d
+    inc c
+    jnz d L13
+
+    tgl c
+
+    cpy -16 c
+    jnz 1 c
+
+    cpy 89 c
+
+L20:
+    jnz 77 d
+
+L21:
+    # a -= d
+    inc a
+    inc d
+    jnz d L21
+
+    inc c
+    jnz c L20
+",
+            program.to_string()
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        let programs = [
+            DAY_23,
+            "cpy 41 a\ninc a\ninc a\ndec a\njnz a 2\ndec a",
+            "jnz 0 0\njnz 1 -100\njnz a 3\nout a\ntgl b\njnz 1 2147483647",
+            "jnz 1 3\ninc a\nout a",
+            "",
+        ];
+
+        for source in programs {
+            let program = parse_program(source).unwrap();
+
+            assert_eq!(Ok(&program), parse_program(&program.to_string()).as_ref());
+        }
+    }
+}
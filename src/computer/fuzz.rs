@@ -0,0 +1,281 @@
+use std::fmt::{Display, Formatter};
+use std::ops::Not;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use super::reference::Interpreter;
+use super::{parse_program, Computer, Outcome, Register, RegisterFile, RunConfig};
+
+const REGISTERS: [&str; 4] = ["a", "b", "c", "d"];
+
+/// Generates small random assembunny programs for the default architecture.
+///
+/// Besides single instructions it emits the loop idioms the optimizer recognises, with random
+/// registers and directions, so that fusing them gets exercised too.
+#[derive(Debug, Clone)]
+pub struct Generator {
+    /// The mnemonics of the single instructions to pick from.
+    pub mnemonics: Vec<&'static str>,
+    /// The number of single instructions and idioms in a program.
+    pub len: usize,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self {
+            mnemonics: vec!["cpy", "inc", "dec", "jnz", "tgl", "out"],
+            len: 5,
+        }
+    }
+}
+
+impl Generator {
+    pub fn source(&self, rng: &mut impl Rng) -> String {
+        (0..self.len)
+            .flat_map(|_| match rng.gen_range(0..6) {
+                0 => self.add_idiom(rng),
+                1 => self.mul_idiom(rng),
+                _ => vec![self.instruction(rng)],
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Initial values for some of the registers.
+    pub fn overrides(&self, rng: &mut impl Rng) -> Vec<(Register, i64)> {
+        let mut overrides = vec![];
+        for register in RegisterFile::default().registers() {
+            if rng.gen_bool(0.5) {
+                overrides.push((register, rng.gen_range(-3..=8)));
+            }
+        }
+        overrides
+    }
+
+    fn instruction(&self, rng: &mut impl Rng) -> String {
+        match *self.mnemonics.choose(rng).unwrap() {
+            "cpy" => format!("cpy {} {}", value(rng), register(rng)),
+            "jnz" => {
+                let offset = match rng.gen_bool(0.8) {
+                    true => rng.gen_range(-3..=5).to_string(),
+                    false => register(rng).to_string(),
+                };
+                format!("jnz {} {}", value(rng), offset)
+            }
+            "out" => format!("out {}", value(rng)),
+            mnemonic => format!("{} {}", mnemonic, register(rng)),
+        }
+    }
+
+    /// An [`Idiom::Add`](super::optimizer::Idiom::Add) loop, usually with its counter set up to reach zero.
+    fn add_idiom(&self, rng: &mut impl Rng) -> Vec<String> {
+        let (body, counter) = add_loop(rng);
+
+        [counter_setup(rng, counter), body].concat()
+    }
+
+    /// An [`Idiom::Mul`](super::optimizer::Idiom::Mul) loop, usually with its outer counter set up to
+    /// reach zero.
+    fn mul_idiom(&self, rng: &mut impl Rng) -> Vec<String> {
+        let outer = register(rng);
+        let (body, counter) = add_loop(rng);
+        let source = match rng.gen_bool(0.7) {
+            true => rng.gen_range(1..=4).to_string(),
+            false => register(rng).to_string(),
+        };
+
+        [
+            counter_setup(rng, outer),
+            vec![format!("cpy {} {}", source, counter)],
+            body,
+            vec![
+                format!("{} {}", countdown(rng), outer),
+                format!("jnz {} -5", outer),
+            ],
+        ]
+        .concat()
+    }
+}
+
+/// The three instructions of an add loop, and its counter.
+fn add_loop(rng: &mut impl Rng) -> (Vec<String>, &'static str) {
+    let (target, counter) = two_registers(rng);
+    let mut body = vec![
+        format!("{} {}", direction(rng), target),
+        format!("{} {}", countdown(rng), counter),
+    ];
+    body.shuffle(rng);
+    body.push(format!("jnz {} -2", counter));
+
+    (body, counter)
+}
+
+fn register(rng: &mut impl Rng) -> &'static str {
+    REGISTERS.choose(rng).unwrap()
+}
+
+fn two_registers(rng: &mut impl Rng) -> (&'static str, &'static str) {
+    let mut registers = REGISTERS.choose_multiple(rng, 2);
+    (registers.next().unwrap(), registers.next().unwrap())
+}
+
+fn value(rng: &mut impl Rng) -> String {
+    match rng.gen_bool(0.5) {
+        true => rng.gen_range(-2..=6).to_string(),
+        false => register(rng).to_string(),
+    }
+}
+
+fn direction(rng: &mut impl Rng) -> &'static str {
+    ["inc", "dec"].choose(rng).unwrap()
+}
+
+/// Mostly `dec`, counting a positive counter down to zero.
+fn countdown(rng: &mut impl Rng) -> &'static str {
+    ["inc", "dec", "dec", "dec", "dec"].choose(rng).unwrap()
+}
+
+/// Mostly sets the counter of a loop to a small positive value.
+fn counter_setup(rng: &mut impl Rng, counter: &str) -> Vec<String> {
+    match rng.gen_bool(0.8) {
+        true => vec![format!("cpy {} {}", rng.gen_range(1..=4), counter)],
+        false => vec![],
+    }
+}
+
+/// A program behaving differently when optimized, or differently from the reference interpreter.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Mismatch {
+    pub source: String,
+    pub overrides: Vec<(Register, i64)>,
+    pub reason: String,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let overrides = self
+            .overrides
+            .iter()
+            .map(|(r, v)| format!("{}={}", r, v))
+            .collect::<Vec<_>>();
+
+        writeln!(f, "{} (with {})", self.reason, overrides.join(" "))?;
+        write!(f, "{}", self.source)
+    }
+}
+
+/// Runs the program for at most `max_steps` steps without optimizations, and compares that with the
+/// optimized [`Computer`] and (when it halted or waits for input) the reference [`Interpreter`].
+///
+/// Returns how the unoptimized run ended, the optimized run ends the same way, being allowed as many
+/// (possibly fused) steps. When the budget runs out its output only has to start with the unoptimized one.
+pub fn compare(
+    source: &str,
+    overrides: &[(Register, i64)],
+    max_steps: usize,
+) -> Result<Outcome, Mismatch> {
+    let mismatch = |reason: String| Mismatch {
+        source: source.to_string(),
+        overrides: overrides.to_vec(),
+        reason,
+    };
+    let program = parse_program(source).map_err(|e| mismatch(e.to_string()))?;
+    let run = |optimize| {
+        Computer::from_program(program.clone(), overrides.to_vec())
+            .run_with(RunConfig {
+                optimize,
+                max_steps: Some(max_steps),
+                ..RunConfig::default()
+            })
+            .map_err(|e| mismatch(e.to_string()))
+    };
+
+    let plain = run(false)?;
+    let optimized = run(true)?;
+
+    let outcome = plain.outcome;
+    let (plain, optimized) = (plain.computer, optimized.computer);
+
+    if outcome == Outcome::StepLimit {
+        let prefix = optimized.output.starts_with(&plain.output);
+        if prefix.not() {
+            return Err(mismatch(format!(
+                "optimized output {:?} doesn't start with {:?}",
+                optimized.output, plain.output
+            )));
+        }
+        return Ok(outcome);
+    }
+
+    let state = |c: &Computer| (c.ip(), c.registers.clone(), c.output.clone(), c.listing());
+    if state(&plain) != state(&optimized) {
+        return Err(mismatch(format!(
+            "optimized {:?}, expected {:?}",
+            state(&optimized),
+            state(&plain)
+        )));
+    }
+
+    let reference = Interpreter::new(program, overrides.to_vec())
+        .run(usize::MAX)
+        .map_err(|e| mismatch(format!("reference {}", e)))?;
+    let registers = RegisterFile::default()
+        .registers()
+        .iter()
+        .map(|r| reference.registers[r])
+        .collect::<Vec<_>>();
+    if (registers.as_slice(), &reference.output) != (plain.registers.values(), &plain.output) {
+        return Err(mismatch(format!(
+            "reference {:?} {:?}, expected {:?} {:?}",
+            registers,
+            reference.output,
+            plain.registers.values(),
+            plain.output
+        )));
+    }
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    const PROGRAMS: u64 = 1_000;
+    const MAX_STEPS: usize = 5_000;
+
+    /// Runs random programs, returning how many of them halted.
+    fn fuzz(generator: &Generator) -> usize {
+        (0..PROGRAMS)
+            .map(|seed| {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let source = generator.source(&mut rng);
+                let overrides = generator.overrides(&mut rng);
+
+                compare(&source, &overrides, MAX_STEPS)
+                    .unwrap_or_else(|mismatch| panic!("seed {}: {}", seed, mismatch))
+            })
+            .filter(|outcome| *outcome == Outcome::Halted)
+            .count()
+    }
+
+    #[test]
+    fn optimizations_match_reference_semantics() {
+        assert!(fuzz(&Generator::default()) > PROGRAMS as usize / 4);
+    }
+
+    #[test]
+    fn reports_mismatches() {
+        let mismatch = compare("inc a\nmul a b", &[(Register::B, 2)], 10).unwrap_err();
+
+        assert_eq!(
+            "line 2, column 1: unexpected `mul`, expected one of `cpy`, `inc`, `dec`, `jnz`, `tgl`, \
+             `in`, `out` (with b=2)\ninc a\nmul a b",
+            mismatch.to_string()
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Not;
+
+use super::{Computer, OverflowError};
+
+/// Steps a computer gets to run before the next one gets its turn.
+const QUANTUM: usize = 1_000;
+
+/// Several [`Computer`]s running side by side, where links pass the values one of them outputs on to
+/// the input of another, to be read with `in`.
+#[derive(Debug, Clone)]
+pub struct Network {
+    computers: Vec<Computer>,
+    links: Vec<(usize, usize)>,
+    /// The number of outputs of each computer already passed on over its links.
+    forwarded: Vec<usize>,
+}
+
+impl Network {
+    pub fn new(computers: Vec<Computer>) -> Self {
+        let forwarded = vec![0; computers.len()];
+
+        Self {
+            computers,
+            links: vec![],
+            forwarded,
+        }
+    }
+
+    /// Passes everything computer `from` outputs on to computer `to`, a computer can feed several others.
+    ///
+    /// Panics when either computer is not part of the network.
+    pub fn connect(mut self, from: usize, to: usize) -> Self {
+        let len = self.computers.len();
+        assert!(from < len && to < len, "no link {} -> {}", from, to);
+
+        self.links.push((from, to));
+        self
+    }
+
+    pub fn computers(&self) -> &[Computer] {
+        &self.computers
+    }
+
+    /// Runs the computers in turns until all of them halted, until the ones that didn't are all waiting
+    /// for input that will never come, or until `max_steps` steps were executed in total.
+    ///
+    /// Fails when a value overflows in one of the computers, leaving the network as it was then.
+    pub fn run(&mut self, optimize: bool, max_steps: usize) -> Result<NetworkStatus, NetworkError> {
+        let mut steps = 0;
+
+        loop {
+            let mut progress = false;
+
+            for idx in 0..self.computers.len() {
+                let computer = &mut self.computers[idx];
+
+                for _ in 0..QUANTUM.min(max_steps - steps) {
+                    if computer.is_halted() || computer.is_waiting() {
+                        break;
+                    }
+                    computer.step(optimize).map_err(|error| NetworkError {
+                        computer: idx,
+                        error,
+                    })?;
+                    steps += 1;
+                    progress = true;
+                }
+
+                self.forward(idx);
+            }
+
+            if self.computers.iter().all(|c| c.is_halted()) {
+                return Ok(NetworkStatus::Halted);
+            }
+            if steps >= max_steps {
+                return Ok(NetworkStatus::StepLimit);
+            }
+            if progress.not() {
+                return Ok(NetworkStatus::Deadlocked);
+            }
+        }
+    }
+
+    /// Passes the new outputs of the computer on over its links.
+    fn forward(&mut self, from: usize) {
+        let output = self.computers[from].output[self.forwarded[from]..].to_vec();
+        self.forwarded[from] += output.len();
+
+        for &(_, to) in self.links.iter().filter(|(f, _)| *f == from) {
+            self.computers[to].input.extend(&output);
+        }
+    }
+}
+
+/// Why [`Network::run`] stopped.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum NetworkStatus {
+    /// Every computer halted.
+    Halted,
+    /// Every computer still running waits for input, and no values are on their way.
+    Deadlocked,
+    /// The computers are still running.
+    StepLimit,
+}
+
+/// A value overflowed in one of the computers of a [`Network`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct NetworkError {
+    pub computer: usize,
+    pub error: OverflowError,
+}
+
+impl Display for NetworkError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "computer {}: {}", self.computer, self.error)
+    }
+}
+
+impl Error for NetworkError {}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Register;
+    use super::*;
+
+    fn computer(code: &[&str]) -> Computer {
+        Computer::new(code, vec![]).unwrap()
+    }
+
+    #[test]
+    fn passes_values_down_a_pipeline() {
+        // Outputs 5, 4, 3, 2, 1
+        let producer = computer(&["cpy 5 a", "out a", "dec a", "jnz a -2"]);
+        // Doubles every value, stopping at the last one
+        let doubler = computer(&["in a", "out a", "out a", "dec a", "jnz a -4"]);
+        // Sums pairs of values into `b`
+        let consumer = computer(&["in a", "inc b", "dec a", "jnz a -2", "jnz 1 -4"]);
+
+        let mut network = Network::new(vec![producer, doubler, consumer])
+            .connect(0, 1)
+            .connect(1, 2);
+
+        assert_eq!(Ok(NetworkStatus::Deadlocked), network.run(true, 10_000));
+        assert_eq!(
+            vec![5, 5, 4, 4, 3, 3, 2, 2, 1, 1],
+            network.computers()[1].output
+        );
+        assert!(network.computers()[1].is_halted());
+        assert!(network.computers()[2].is_waiting());
+        assert_eq!(30, network.computers()[2].registers[&Register::B]);
+    }
+
+    #[test]
+    fn runs_a_ring_until_it_halts_or_the_step_limit() {
+        // Passes an increasing value back and forth 5 times, then sends 0 to stop the other computer
+        let ping = computer(&[
+            "cpy 5 d", "out 1", "in a", "inc a", "dec d", "jnz d 2", "jnz 1 3", "out a",
+            "jnz 1 -6", "out 0",
+        ]);
+        let pong = computer(&["in a", "jnz a 2", "jnz 1 4", "inc a", "out a", "jnz 1 -5"]);
+
+        let mut network = Network::new(vec![ping, pong]).connect(0, 1).connect(1, 0);
+        assert_eq!(Ok(NetworkStatus::Halted), network.run(true, 10_000));
+        assert_eq!(11, network.computers()[0].registers[&Register::A]);
+        assert_eq!(vec![2, 4, 6, 8, 10], network.computers()[1].output);
+
+        let ping = computer(&["out 1", "in a", "out a", "jnz 1 -2"]);
+        let pong = computer(&["in a", "inc a", "out a", "jnz 1 -3"]);
+
+        let mut network = Network::new(vec![ping, pong]).connect(0, 1).connect(1, 0);
+        assert_eq!(Ok(NetworkStatus::StepLimit), network.run(true, 100));
+        assert!(network.computers()[0].output.len() > 10);
+    }
+
+    #[test]
+    fn runs_nothing_without_steps() {
+        let mut network =
+            Network::new(vec![computer(&["out 1"]), computer(&["in a"])]).connect(0, 1);
+
+        assert_eq!(Ok(NetworkStatus::StepLimit), network.run(true, 0));
+        assert!(network.computers()[0].output.is_empty());
+        assert_eq!(Ok(NetworkStatus::Halted), network.run(true, 10));
+    }
+}
@@ -0,0 +1,323 @@
+use std::fmt::{Display, Formatter};
+
+use super::{Instruction, Register, Registers, Value, Word};
+
+/// Length of the longest idiom, an [`Idiom::Mul`].
+const MAX_LEN: usize = 6;
+
+/// A loop idiom recognised in assembunny code, executed as one fused operation instead of
+/// stepping through every iteration.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Idiom {
+    /// `inc target` (or `dec target`) and `dec counter` (or `inc counter`), in either order,
+    /// followed by `jnz counter -2`.
+    Add {
+        target: Register,
+        delta: i64,
+        counter: Register,
+        step: i64,
+    },
+    /// `cpy source inner`, an [`Idiom::Add`] loop draining `inner` into `target`,
+    /// then `dec outer` (or `inc outer`) followed by `jnz outer -5`.
+    Mul {
+        target: Register,
+        delta: i64,
+        source: Value,
+        inner: Register,
+        inner_step: i64,
+        outer: Register,
+        outer_step: i64,
+    },
+}
+
+impl Idiom {
+    /// Number of instructions covered by the idiom, the fused operation continues right after them.
+    pub fn len(&self) -> usize {
+        match self {
+            Idiom::Add { .. } => 3,
+            Idiom::Mul { .. } => 6,
+        }
+    }
+
+    /// Executes the idiom starting at `ip` and returns the next instruction pointer.
+    ///
+    /// Returns `None` (leaving the registers untouched) when the counters would not move towards zero,
+    /// in that case the loop does not terminate the "normal" way and has to be stepped through.
+    /// The same goes for overflowing a checked `word`, so that stepping reports the exact instruction.
+    pub fn apply(&self, registers: &mut Registers, word: Word, ip: usize) -> Option<usize> {
+        match *self {
+            Idiom::Add {
+                target,
+                delta,
+                counter,
+                step,
+            } => {
+                let iterations = iterations(registers[&counter], step)?;
+                let total = word.add(registers[&target], word.mul(delta, iterations)?)?;
+
+                registers[&target] = total;
+                registers[&counter] = 0;
+            }
+            Idiom::Mul {
+                target,
+                delta,
+                source,
+                inner,
+                inner_step,
+                outer,
+                outer_step,
+            } => {
+                let source = match source {
+                    Value::R { register } => registers[&register],
+                    Value::C { constant } => constant,
+                };
+                let inner_iterations = iterations(source, inner_step)?;
+                let outer_iterations = iterations(registers[&outer], outer_step)?;
+                let product = word.mul(word.mul(delta, inner_iterations)?, outer_iterations)?;
+                let total = word.add(registers[&target], product)?;
+
+                registers[&target] = total;
+                registers[&inner] = 0;
+                registers[&outer] = 0;
+            }
+        }
+
+        Some(ip + self.len())
+    }
+}
+
+/// Pseudo-code for the effect of the idiom, e.g. `a += b * d`.
+impl Display for Idiom {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (sign, target, factors) = match *self {
+            Idiom::Add {
+                target,
+                delta,
+                counter,
+                step,
+            } => (-delta * step, target, counter.to_string()),
+            Idiom::Mul {
+                target,
+                delta,
+                source,
+                inner_step,
+                outer,
+                outer_step,
+                ..
+            } => (
+                delta * inner_step * outer_step,
+                target,
+                format!("{} * {}", source, outer),
+            ),
+        };
+        let operator = if sign > 0 { "+=" } else { "-=" };
+
+        write!(f, "{} {} {}", target, operator, factors)
+    }
+}
+
+/// Scans the whole program and returns, for every instruction index, the idiom starting there (if any).
+pub fn detect(instructions: &[Instruction]) -> Vec<Option<Idiom>> {
+    (0..instructions.len())
+        .map(|ip| detect_at(&instructions[ip..]))
+        .collect()
+}
+
+/// Re-validates the idioms that could cover the instruction at `idx`, after it changed.
+pub fn refresh(idioms: &mut [Option<Idiom>], instructions: &[Instruction], idx: usize) {
+    for ip in idx.saturating_sub(MAX_LEN - 1)..=idx {
+        idioms[ip] = detect_at(&instructions[ip..]);
+    }
+}
+
+fn detect_at(instructions: &[Instruction]) -> Option<Idiom> {
+    detect_mul(instructions).or_else(|| detect_add(instructions))
+}
+
+fn detect_add(instructions: &[Instruction]) -> Option<Idiom> {
+    let [first, second, Instruction::Jnz {
+        value: Value::R { register: counter },
+        offset: Value::C { constant: -2 },
+        valid: true,
+    }, ..] = instructions
+    else {
+        return None;
+    };
+
+    let (first, first_delta) = increment(first)?;
+    let (second, second_delta) = increment(second)?;
+
+    let (target, delta, step) = if first == *counter && second != *counter {
+        (second, second_delta, first_delta)
+    } else if second == *counter && first != *counter {
+        (first, first_delta, second_delta)
+    } else {
+        return None;
+    };
+
+    Some(Idiom::Add {
+        target,
+        delta,
+        counter: *counter,
+        step,
+    })
+}
+
+fn detect_mul(instructions: &[Instruction]) -> Option<Idiom> {
+    let [Instruction::Cpy { from, to }, _, _, _, outer_increment, Instruction::Jnz {
+        value: Value::R { register: outer },
+        offset: Value::C { constant: -5 },
+        valid: true,
+    }, ..] = instructions
+    else {
+        return None;
+    };
+
+    let Some(Idiom::Add {
+        target,
+        delta,
+        counter,
+        step,
+    }) = detect_add(&instructions[1..])
+    else {
+        return None;
+    };
+    let (outer_register, outer_step) = increment(outer_increment)?;
+
+    let registers = [target, counter, *outer];
+    let distinct = target != *outer && counter != *outer;
+    let source_untouched = match from {
+        Value::R { register } => !registers.contains(register),
+        Value::C { .. } => true,
+    };
+
+    if *to != counter || outer_register != *outer || !distinct || !source_untouched {
+        return None;
+    }
+
+    Some(Idiom::Mul {
+        target,
+        delta,
+        source: *from,
+        inner: counter,
+        inner_step: step,
+        outer: *outer,
+        outer_step,
+    })
+}
+
+fn increment(instruction: &Instruction) -> Option<(Register, i64)> {
+    match instruction {
+        Instruction::Inc { register } => Some((*register, 1)),
+        Instruction::Dec { register } => Some((*register, -1)),
+        _ => None,
+    }
+}
+
+/// How many times a counter starting at `start` has to move by `step` to reach zero,
+/// only when it reaches zero without wrapping around.
+fn iterations(start: i64, step: i64) -> Option<i64> {
+    let iterations = start.checked_mul(-step)?;
+
+    (iterations > 0).then_some(iterations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{parse_program, Computer};
+    use super::*;
+
+    fn parse(code: &[&str]) -> Vec<Instruction> {
+        parse_program(&code.join("\n")).unwrap().instructions
+    }
+
+    #[test]
+    fn detects_addition() {
+        let idioms = detect(&parse(&["cpy 5 b", "dec b", "inc a", "jnz b -2"]));
+
+        assert_eq!(
+            vec![
+                None,
+                Some(Idiom::Add {
+                    target: Register::A,
+                    delta: 1,
+                    counter: Register::B,
+                    step: -1,
+                }),
+                None,
+                None,
+            ],
+            idioms
+        );
+    }
+
+    #[test]
+    fn detects_multiplication() {
+        let idioms = detect(&parse(&[
+            "cpy b c", "inc a", "dec c", "jnz c -2", "dec d", "jnz d -5",
+        ]));
+
+        assert_eq!(
+            Some(Idiom::Mul {
+                target: Register::A,
+                delta: 1,
+                source: Value::R {
+                    register: Register::B
+                },
+                inner: Register::C,
+                inner_step: -1,
+                outer: Register::D,
+                outer_step: -1,
+            }),
+            idioms[0]
+        );
+        assert!(matches!(idioms[1], Some(Idiom::Add { .. })));
+    }
+
+    #[test]
+    fn rejects_multiplication_reading_its_own_registers() {
+        let idioms = detect(&parse(&[
+            "cpy a c", "inc a", "dec c", "jnz c -2", "dec d", "jnz d -5",
+        ]));
+
+        assert_eq!(None, idioms[0]);
+    }
+
+    #[test]
+    fn formats_as_pseudo_code() {
+        let idioms = detect(&parse(&[
+            "cpy 4 c", "dec c", "dec a", "jnz c -2", "inc d", "jnz d -5", "inc b", "inc a",
+            "jnz a -2",
+        ]));
+
+        assert_eq!("a += 4 * d", idioms[0].unwrap().to_string());
+        assert_eq!("a -= c", idioms[1].unwrap().to_string());
+        assert_eq!("b -= a", idioms[6].unwrap().to_string());
+    }
+
+    #[test]
+    fn optimized_run_matches_plain_run() {
+        let programs = [
+            vec!["cpy 7 b", "inc a", "dec b", "jnz b -2"],
+            vec!["cpy -7 b", "dec a", "inc b", "jnz b -2"],
+            vec![
+                "cpy 4 b", "cpy 6 d", "cpy b c", "inc a", "dec c", "jnz c -2", "dec d", "jnz d -5",
+            ],
+            vec![
+                "cpy 3 a", "cpy 5 d", "cpy 4 c", "dec c", "dec a", "jnz c -2", "dec d", "jnz d -5",
+            ],
+            vec![
+                "cpy 2 a", "tgl a", "tgl a", "tgl a", "cpy 1 a", "dec a", "dec a",
+            ],
+            vec!["cpy 3 b", "tgl b", "inc a", "dec b", "jnz b -2"],
+        ];
+
+        for program in programs {
+            let computer = Computer::new(&program, vec![]).unwrap();
+            let plain = computer.clone().run(false, 0).unwrap();
+            let optimized = computer.run(true, 0).unwrap();
+
+            assert_eq!(plain.registers, optimized.registers, "{:?}", program);
+        }
+    }
+}
@@ -0,0 +1,546 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Not;
+use std::str::FromStr;
+
+use itertools::Itertools;
+use rustc_hash::FxHashMap;
+
+use super::{Architecture, Instruction, Program, Register, RegisterFile, Value};
+
+const COMMENT: char = '#';
+const LABEL_SUFFIX: char = ':';
+pub(super) const MNEMONICS: [&str; 7] = ["cpy", "inc", "dec", "jnz", "tgl", "in", "out"];
+pub(super) const INTEGER: &str = "integer";
+pub(super) const LABEL: &str = "label";
+pub(super) const NEW_LABEL: &str = "label not defined before";
+pub(super) const NAME: &str = "name";
+pub(super) const NEW_NAME: &str = "name not defined before";
+pub(super) const ARGUMENT: &str = "argument";
+pub(super) const NON_RECURSIVE_MACRO: &str = "macro not being expanded";
+pub(super) const CLOSED_MACRO: &str = "macro closed by `end`";
+pub(super) const END_OF_LINE: &str = "end of line";
+/// Alternatives describing a kind of token, rather than being the literal token itself.
+const DESCRIPTIONS: [&str; 9] = [
+    INTEGER,
+    LABEL,
+    NEW_LABEL,
+    NAME,
+    NEW_NAME,
+    ARGUMENT,
+    NON_RECURSIVE_MACRO,
+    CLOSED_MACRO,
+    END_OF_LINE,
+];
+
+/// Parses assembunny source code, one instruction per line.
+///
+/// Tokens can be separated by any amount of whitespace, everything after a `#` is a comment.
+/// Blank and comment-only lines are skipped and do not count as instructions for `jnz` offsets.
+///
+/// A line can start with a label definition (`loop:`), which names the next instruction.
+/// The offset of a `jnz` can refer to such a label instead of a relative number of instructions.
+pub fn parse_program(source: &str) -> Result<Program, ParseError> {
+    parse_program_for(source, Architecture::default())
+}
+
+/// Parses assembunny source code like [`parse_program`], for a machine with other registers or words.
+///
+/// Only the registers of the architecture are accepted, and constants have to fit its word.
+pub fn parse_program_for(source: &str, architecture: Architecture) -> Result<Program, ParseError> {
+    let statements = tokenize(source)
+        .into_iter()
+        .map(|tokens| Statement {
+            scope: GLOBAL_SCOPE,
+            tokens,
+        })
+        .collect();
+
+    parse_statements(statements, architecture)
+}
+
+/// Splits source code in lines of tokens, leaving out comments.
+pub(super) fn tokenize(source: &str) -> Vec<Tokens<'_>> {
+    source
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| Tokens::new(idx + 1, line.split(COMMENT).next().unwrap_or_default()))
+        .collect()
+}
+
+pub(super) const GLOBAL_SCOPE: usize = 0;
+
+/// A line of tokens, labels referred to are looked up in its own scope first and the global scope second.
+pub(super) struct Statement<'a> {
+    pub scope: usize,
+    pub tokens: Tokens<'a>,
+}
+
+pub(super) fn parse_statements(
+    mut statements: Vec<Statement>,
+    architecture: Architecture,
+) -> Result<Program, ParseError> {
+    let mut labels = FxHashMap::default();
+    let mut instruction_count = 0;
+    for statement in &mut statements {
+        statement.tokens.architecture = architecture;
+        if let Some((token, label)) = statement.tokens.label()? {
+            if labels
+                .insert((statement.scope, label), instruction_count)
+                .is_some()
+            {
+                return Err(token.unexpected(&[NEW_LABEL]));
+            }
+        }
+        if statement.tokens.is_done().not() {
+            instruction_count += 1;
+        }
+    }
+
+    let mut instructions = vec![];
+    for mut statement in statements {
+        let labels = |label| {
+            labels
+                .get(&(statement.scope, label))
+                .or_else(|| labels.get(&(GLOBAL_SCOPE, label)))
+                .copied()
+        };
+
+        if let Some(instruction) = parse_line(&mut statement.tokens, labels, instructions.len())? {
+            instructions.push(instruction);
+        }
+    }
+
+    Ok(Program {
+        instructions,
+        architecture,
+    })
+}
+
+fn parse_line<'a>(
+    tokens: &mut Tokens<'a>,
+    labels: impl Fn(&'a str) -> Option<usize>,
+    idx: usize,
+) -> Result<Option<Instruction>, ParseError> {
+    let Some(mnemonic) = tokens.next() else {
+        return Ok(None);
+    };
+
+    let instruction = match mnemonic.text {
+        "cpy" => Instruction::Cpy {
+            from: tokens.value()?,
+            to: tokens.register()?,
+        },
+        "inc" => Instruction::Inc {
+            register: tokens.register()?,
+        },
+        "dec" => Instruction::Dec {
+            register: tokens.register()?,
+        },
+        "jnz" => Instruction::Jnz {
+            value: tokens.value()?,
+            offset: tokens.offset(labels, idx)?,
+            valid: true,
+        },
+        "tgl" => Instruction::Tgl {
+            register: tokens.register()?,
+        },
+        "in" => Instruction::In {
+            register: tokens.register()?,
+        },
+        "out" => Instruction::Out {
+            value: tokens.value()?,
+        },
+        _ => return Err(mnemonic.unexpected(&MNEMONICS)),
+    };
+
+    tokens.end()?;
+
+    Ok(Some(instruction))
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    /// 1-based line number in the source.
+    pub line: usize,
+    /// 1-based column of the offending token.
+    pub column: usize,
+    /// The offending token, empty when the line ended too early.
+    pub token: String,
+    pub expected: Vec<String>,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let found = if self.token.is_empty() {
+            END_OF_LINE.to_string()
+        } else {
+            format!("`{}`", self.token)
+        };
+        let expected = self
+            .expected
+            .iter()
+            .map(|e| {
+                if DESCRIPTIONS.contains(&e.as_str()) {
+                    e.to_string()
+                } else {
+                    format!("`{}`", e)
+                }
+            })
+            .join(", ");
+        let one_of = if self.expected.len() > 1 {
+            "one of "
+        } else {
+            ""
+        };
+
+        write!(
+            f,
+            "line {}, column {}: unexpected {}, expected {}{}",
+            self.line, self.column, found, one_of, expected
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug, Copy, Clone)]
+pub(super) struct Token<'a> {
+    pub line: usize,
+    pub column: usize,
+    pub text: &'a str,
+}
+
+impl Token<'_> {
+    pub(super) fn unexpected(&self, expected: &[&str]) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            token: self.text.to_string(),
+            expected: expected.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Tokens<'a> {
+    pub tokens: Vec<Token<'a>>,
+    /// Stand-in token right after the last one, to report missing tokens.
+    pub end: Token<'a>,
+    position: usize,
+    /// The registers and constants allowed.
+    architecture: Architecture,
+}
+
+impl<'a> Tokens<'a> {
+    pub(super) fn from_parts(tokens: Vec<Token<'a>>, end: Token<'a>) -> Self {
+        Self {
+            tokens,
+            end,
+            position: 0,
+            architecture: Architecture::default(),
+        }
+    }
+
+    fn new(line: usize, code: &'a str) -> Self {
+        let mut tokens = vec![];
+        let mut start: Option<usize> = None;
+
+        for (idx, c) in code.char_indices().chain([(code.len(), ' ')]) {
+            match (start, c.is_whitespace()) {
+                (None, false) => start = Some(idx),
+                (Some(s), true) => {
+                    tokens.push(Token {
+                        line,
+                        column: code[..s].chars().count() + 1,
+                        text: &code[s..idx],
+                    });
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        let end = Token {
+            line,
+            column: code.trim_end().chars().count() + 1,
+            text: "",
+        };
+
+        Self::from_parts(tokens, end)
+    }
+
+    fn is_done(&self) -> bool {
+        self.position >= self.tokens.len()
+    }
+
+    /// Consumes the label definition the line starts with, if there is one.
+    fn label(&mut self) -> Result<Option<(Token<'a>, &'a str)>, ParseError> {
+        let Some(token) = self.tokens.first().copied() else {
+            return Ok(None);
+        };
+        let Some(label) = token.text.strip_suffix(LABEL_SUFFIX) else {
+            return Ok(None);
+        };
+
+        if is_label(label, self.architecture.registers).not() {
+            return Err(token.unexpected(&[LABEL]));
+        }
+
+        self.position += 1;
+        Ok(Some((token, label)))
+    }
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let token = self.tokens.get(self.position).copied();
+        self.position += 1;
+        token
+    }
+
+    fn register(&mut self) -> Result<Register, ParseError> {
+        let token = self.next().unwrap_or(self.end);
+
+        Register::from_name(token.text)
+            .filter(|r| self.architecture.registers.contains(*r))
+            .ok_or_else(|| self.unexpected(token, &[]))
+    }
+
+    fn value(&mut self) -> Result<Value, ParseError> {
+        let token = self.next().unwrap_or(self.end);
+
+        let word = self.architecture.word;
+        if let Some(constant) = i64::from_str(token.text)
+            .ok()
+            .filter(|c| (word.min()..=word.max()).contains(c))
+        {
+            return Ok(Value::C { constant });
+        }
+
+        self.position -= 1;
+        self.register()
+            .map(|register| Value::R { register })
+            .map_err(|_| self.unexpected(token, &[INTEGER]))
+    }
+
+    /// Error for a token that is neither a register nor any of the other expected alternatives.
+    fn unexpected(&self, token: Token, alternatives: &[&str]) -> ParseError {
+        let registers = register_names(self.architecture.registers);
+        let registers = registers.iter().map(String::as_str);
+
+        token.unexpected(&registers.chain(alternatives.iter().copied()).collect_vec())
+    }
+
+    /// A `jnz` offset, either a value or a label turned into the offset relative to instruction `idx`.
+    fn offset(
+        &mut self,
+        labels: impl Fn(&'a str) -> Option<usize>,
+        idx: usize,
+    ) -> Result<Value, ParseError> {
+        let token = self.tokens.get(self.position).copied().unwrap_or(self.end);
+
+        match labels(token.text) {
+            Some(target) => {
+                self.position += 1;
+                Ok(Value::C {
+                    constant: target as i64 - idx as i64,
+                })
+            }
+            None => self
+                .value()
+                .map_err(|_| self.unexpected(token, &[INTEGER, LABEL])),
+        }
+    }
+
+    fn end(&mut self) -> Result<(), ParseError> {
+        match self.next() {
+            None => Ok(()),
+            Some(token) => Err(token.unexpected(&[END_OF_LINE])),
+        }
+    }
+}
+
+/// Labels look like identifiers, names of the registers in the file are not allowed.
+pub(super) fn is_label(label: &str, registers: RegisterFile) -> bool {
+    let mut chars = label.chars();
+
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && Register::from_name(label)
+            .is_some_and(|r| registers.contains(r))
+            .not()
+}
+
+/// The names listed as expected for a register: each letter, or the range of numbered registers.
+fn register_names(registers: RegisterFile) -> Vec<String> {
+    match registers {
+        RegisterFile::Letters(_) => registers
+            .registers()
+            .iter()
+            .map(|r| r.to_string())
+            .collect(),
+        RegisterFile::Numbered(_) if registers.is_empty() => vec![],
+        RegisterFile::Numbered(_) => vec![format!("r0..r{}", registers.len() - 1)],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Word;
+    use super::*;
+
+    fn error(line: usize, column: usize, token: &str, expected: &[&str]) -> ParseError {
+        ParseError {
+            line,
+            column,
+            token: token.to_string(),
+            expected: expected.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn parses_with_whitespace_and_comments() {
+        let source = "# Sample program\n  cpy   41 a\n\n\tinc a # add one\njnz a -2\nout  b\n";
+
+        assert_eq!(
+            Ok(Program {
+                instructions: vec![
+                    Instruction::Cpy {
+                        from: Value::C { constant: 41 },
+                        to: Register::A,
+                    },
+                    Instruction::Inc {
+                        register: Register::A,
+                    },
+                    Instruction::Jnz {
+                        value: Value::R {
+                            register: Register::A
+                        },
+                        offset: Value::C { constant: -2 },
+                        valid: true,
+                    },
+                    Instruction::Out {
+                        value: Value::R {
+                            register: Register::B
+                        },
+                    },
+                ],
+                architecture: Architecture::default(),
+            }),
+            parse_program(source)
+        );
+    }
+
+    #[test]
+    fn reports_unknown_mnemonic() {
+        assert_eq!(
+            Err(error(2, 3, "mul", &MNEMONICS)),
+            parse_program("inc a\n  mul a b")
+        );
+    }
+
+    #[test]
+    fn reports_invalid_operands() {
+        assert_eq!(
+            Err(error(1, 5, "e", &["a", "b", "c", "d"])),
+            parse_program("inc e")
+        );
+        assert_eq!(
+            Err(error(1, 5, "4x", &["a", "b", "c", "d", INTEGER])),
+            parse_program("cpy 4x a")
+        );
+        assert_eq!(
+            Err(error(1, 6, "", &["a", "b", "c", "d", INTEGER, LABEL])),
+            parse_program("jnz a  # offset missing")
+        );
+        assert_eq!(
+            Err(error(1, 7, "b", &[END_OF_LINE])),
+            parse_program("dec a b c")
+        );
+    }
+
+    #[test]
+    fn resolves_labels() {
+        let source =
+            "start:\n  inc a\nloop: dec b\n  jnz b loop # back\n  jnz a start\n  jnz 1 end\nend:";
+
+        assert_eq!(
+            parse_program("inc a\ndec b\njnz b -1\njnz a -3\njnz 1 1"),
+            parse_program(source)
+        );
+    }
+
+    #[test]
+    fn reports_invalid_labels() {
+        assert_eq!(
+            Err(error(3, 1, "x:", &[NEW_LABEL])),
+            parse_program("x:\ninc a\nx: dec a")
+        );
+        assert_eq!(Err(error(1, 1, "a:", &[LABEL])), parse_program("a: inc a"));
+        assert_eq!(
+            Err(error(
+                1,
+                7,
+                "nowhere",
+                &["a", "b", "c", "d", INTEGER, LABEL]
+            )),
+            parse_program("jnz 1 nowhere")
+        );
+    }
+
+    #[test]
+    fn parses_for_architectures() {
+        let letters = Architecture {
+            registers: RegisterFile::Letters(5),
+            word: Word::I32,
+        };
+        let numbered = Architecture {
+            registers: RegisterFile::Numbered(8),
+            word: Word::I64,
+        };
+
+        assert!(parse_program_for("inc e\nend: jnz e end", letters).is_ok());
+        assert_eq!(
+            Err(error(1, 1, "e:", &[LABEL])),
+            parse_program_for("e: inc e", letters)
+        );
+        assert_eq!(
+            Err(error(1, 5, "f", &["a", "b", "c", "d", "e"])),
+            parse_program_for("inc f", letters)
+        );
+        assert_eq!(
+            Err(error(
+                1,
+                5,
+                "3000000000",
+                &["a", "b", "c", "d", "e", INTEGER]
+            )),
+            parse_program_for("cpy 3000000000 a", letters)
+        );
+
+        assert!(parse_program_for("cpy 3000000000 r7\na: jnz r0 a", numbered).is_ok());
+        assert_eq!(
+            Err(error(1, 5, "a", &["r0..r7"])),
+            parse_program_for("inc a", numbered)
+        );
+    }
+
+    #[test]
+    fn formats_errors() {
+        assert_eq!(
+            "line 3, column 7: unexpected `x`, expected one of `a`, `b`, `c`, `d`, integer, label",
+            parse_program("jnz a 2\ninc a\njnz 1 x")
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "line 1, column 4: unexpected end of line, expected one of `a`, `b`, `c`, `d`",
+            parse_program("inc  ").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "line 2, column 1: unexpected `x:`, expected label not defined before",
+            parse_program("x: inc a\nx: dec a").unwrap_err().to_string()
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::VecDeque;
+
+use rustc_hash::FxHashMap;
+
+use super::{Instruction, OverflowError, Program, Register, Value, Word};
+
+/// The original, straightforward assembunny interpreter: registers live in a hash map and every
+/// instruction is matched on again each time it gets executed, without any loop optimizations.
+///
+/// Kept around as the reference semantics and the performance baseline for [`super::Computer`].
+/// Values follow the [`Word`] of the program, wrapping around or overflowing like they do there.
+#[derive(Debug, Clone)]
+pub struct Interpreter {
+    pub registers: FxHashMap<Register, i64>,
+    pub input: VecDeque<i64>,
+    pub output: Vec<i64>,
+    word: Word,
+    instructions: Vec<Instruction>,
+}
+
+impl Interpreter {
+    pub fn new(program: Program, overrides: Vec<(Register, i64)>) -> Self {
+        let word = program.architecture.word;
+        let registers = program
+            .architecture
+            .registers
+            .registers()
+            .into_iter()
+            .map(|r| (r, 0))
+            .chain(
+                overrides
+                    .into_iter()
+                    .map(|(r, value)| (r, word.wrap(value))),
+            )
+            .collect();
+
+        Self {
+            registers,
+            input: VecDeque::new(),
+            output: vec![],
+            word,
+            instructions: program.instructions,
+        }
+    }
+
+    /// Runs the program until it halts, waits for input, or produced more than `output_len` values.
+    ///
+    /// Fails when a value overflows a [`Word::Checked`] word.
+    pub fn run(&self, output_len: usize) -> Result<Self, OverflowError> {
+        let mut interpreter = self.clone();
+        let mut ip = 0;
+
+        while ip < interpreter.instructions.len()
+            && interpreter.output.len() <= output_len
+            && !interpreter.is_waiting(ip)
+        {
+            ip = interpreter.step(ip)?;
+        }
+
+        Ok(interpreter)
+    }
+
+    fn is_waiting(&self, ip: usize) -> bool {
+        matches!(self.instructions[ip], Instruction::In { .. }) && self.input.is_empty()
+    }
+
+    fn step(&mut self, ip: usize) -> Result<usize, OverflowError> {
+        let instruction = &self.instructions[ip];
+        let overflow = OverflowError { ip };
+        let ip = ip + 1;
+
+        if let Instruction::Jnz { valid: false, .. } = instruction {
+            return Ok(ip);
+        }
+
+        match instruction {
+            Instruction::Cpy { from, to, .. } => match from {
+                Value::R { register } => {
+                    *self.registers.get_mut(to).unwrap() = self.registers[register]
+                }
+                Value::C { constant } => *self.registers.get_mut(to).unwrap() = *constant,
+            },
+            Instruction::Inc { register, .. } => {
+                let value = self.word.add(self.registers[register], 1).ok_or(overflow)?;
+                *self.registers.get_mut(register).unwrap() = value
+            }
+            Instruction::Dec { register, .. } => {
+                let value = self
+                    .word
+                    .add(self.registers[register], -1)
+                    .ok_or(overflow)?;
+                *self.registers.get_mut(register).unwrap() = value
+            }
+            Instruction::Jnz { value, offset, .. } => {
+                let i_value = match value {
+                    Value::R { register } => self.registers[register],
+                    Value::C { constant } => *constant,
+                };
+                let offset_value = match offset {
+                    Value::R { register } => self.registers[register],
+                    Value::C { constant } => *constant,
+                };
+
+                if i_value != 0 {
+                    // Jumping before the first instruction wraps around, halting the program
+                    return Ok((ip as i64 - 1 + offset_value) as usize);
+                }
+            }
+            Instruction::Tgl { register, .. } => {
+                let idx = ip as i64 - 1 + self.registers[register];
+
+                if idx >= 0 && idx < self.instructions.len() as i64 {
+                    let idx = idx as usize;
+                    self.instructions[idx] = self.instructions[idx].toggle();
+                }
+            }
+            Instruction::In { register } => {
+                *self.registers.get_mut(register).unwrap() =
+                    self.word.wrap(self.input.pop_front().unwrap())
+            }
+            Instruction::Out { value } => {
+                let out_value = match value {
+                    Value::R { register } => self.registers[register],
+                    Value::C { constant } => *constant,
+                };
+                self.output.push(out_value);
+            }
+            // Toggling can turn instructions into invalid ones, which are skipped
+            Instruction::InvalidStep { .. } => {}
+        }
+        Ok(ip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{parse_program, Computer, RegisterFile};
+    use super::*;
+
+    #[test]
+    fn computer_matches_reference() {
+        let programs = [
+            "cpy 41 a\ninc a\ninc a\ndec a\njnz a 2\ndec a",
+            "cpy 2 a\ntgl a\ntgl a\ntgl a\ncpy 1 a\ndec a\ndec a",
+            "cpy 3 b\ncpy b c\ninc a\ndec c\njnz c -2\ndec b\njnz b -5\nout a\njnz 1 -100",
+            "cpy 5 c\nout c\ndec c\njnz c -2\ncpy 1 d\ntgl d\njnz d c\nout d",
+            // `jnz a 2` toggles into `cpy a 2`, which is skipped
+            "cpy 1 b\ntgl b\njnz a 2\ninc a\nout a",
+            "cpy 2147483647 a\ninc a\ncpy -2147483648 b\ndec b\nout a\nout b",
+        ];
+
+        for source in programs {
+            let program = parse_program(source).unwrap();
+            let reference = Interpreter::new(program.clone(), vec![(Register::D, 2)])
+                .run(10)
+                .unwrap();
+            let computer = Computer::from_program(program, vec![(Register::D, 2)])
+                .run(false, 10)
+                .unwrap();
+
+            for register in RegisterFile::default().registers() {
+                assert_eq!(
+                    reference.registers[&register], computer.registers[&register],
+                    "{}",
+                    source
+                );
+            }
+            assert_eq!(reference.output, computer.output, "{}", source);
+        }
+    }
+
+    #[test]
+    fn follows_the_word_on_overflow() {
+        let source = "inc a\ndec b\nout a\nout b";
+        let run = |word, overrides: Vec<(Register, i64)>| {
+            let mut program = parse_program(source).unwrap();
+            program.architecture.word = word;
+            let reference = Interpreter::new(program.clone(), overrides.clone()).run(10);
+            let computer = Computer::from_program(program, overrides).run(false, 10);
+
+            assert_eq!(
+                computer.as_ref().map(|c| c.output.clone()),
+                reference.as_ref().map(|r| r.output.clone())
+            );
+            reference.map(|r| r.output)
+        };
+
+        let (max, min) = (i32::MAX as i64, i32::MIN as i64);
+        assert_eq!(
+            Ok(vec![min, max]),
+            run(Word::I32, vec![(Register::A, max), (Register::B, min)])
+        );
+        assert_eq!(
+            Ok(vec![max + 1, min - 1]),
+            run(Word::I64, vec![(Register::A, max), (Register::B, min)])
+        );
+        assert_eq!(
+            Err(OverflowError { ip: 0 }),
+            run(Word::Checked, vec![(Register::A, max)])
+        );
+        assert_eq!(
+            Err(OverflowError { ip: 1 }),
+            run(Word::Checked, vec![(Register::B, min)])
+        );
+    }
+}
@@ -0,0 +1,420 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use serde_json::{json, Value as Json};
+
+use super::{
+    optimizer, parse_program_for, Architecture, Computer, Instruction, Op, ParseError,
+    RegisterFile, Registers, Value, Word,
+};
+
+/// The full state of a [`Computer`]: instruction pointer, registers, input, output and the instructions
+/// as changed by `tgl`, see [`Computer::snapshot`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Snapshot {
+    architecture: Architecture,
+    ip: usize,
+    registers: Registers,
+    input: VecDeque<i64>,
+    output: Vec<i64>,
+    instructions: Vec<Instruction>,
+}
+
+impl Snapshot {
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    /// A single JSON object, the instructions are listed as assembunny source.
+    ///
+    /// Skipped instructions don't parse: a `jnz` toggled into a `cpy` to a constant, or an `out` of a constant
+    /// toggled into an `inc` or `dec`. They are listed as the `jnz` or `out` they are toggled from, with their
+    /// index under `skipped` once for every toggle needed.
+    pub fn to_json(&self) -> String {
+        let (kind, count) = match self.architecture.registers {
+            RegisterFile::Letters(count) => ("letters", count),
+            RegisterFile::Numbered(count) => ("numbered", count),
+        };
+        let word = match self.architecture.word {
+            Word::I32 => "i32",
+            Word::I64 => "i64",
+            Word::Checked => "checked",
+        };
+        let mut skipped = vec![];
+        let mut instructions = vec![];
+        for (idx, instruction) in self.instructions.iter().enumerate() {
+            let (source, toggles) = match *instruction {
+                Instruction::Jnz {
+                    value,
+                    offset,
+                    valid: false,
+                } => {
+                    let valid = true;
+                    (
+                        Instruction::Jnz {
+                            value,
+                            offset,
+                            valid,
+                        },
+                        1,
+                    )
+                }
+                Instruction::InvalidStep { constant, step } => {
+                    let value = Value::C { constant };
+                    (Instruction::Out { value }, if step == 1 { 1 } else { 2 })
+                }
+                _ => (instruction.clone(), 0),
+            };
+            instructions.push(source.to_string());
+            skipped.extend([idx].repeat(toggles));
+        }
+
+        json!({
+            "architecture": {"registers": kind, "count": count, "word": word},
+            "ip": self.ip,
+            "registers": self.registers.values(),
+            "input": self.input,
+            "output": self.output,
+            "instructions": instructions,
+            "skipped": skipped,
+        })
+        .to_string()
+    }
+
+    /// Reads a snapshot written by [`Snapshot::to_json`].
+    pub fn from_json(json: &str) -> Result<Self, SnapshotError> {
+        let json: Json =
+            serde_json::from_str(json).map_err(|e| SnapshotError::Json(e.to_string()))?;
+
+        let architecture = &json["architecture"];
+        let count = field(&architecture["count"], "architecture.count", Json::as_u64)?;
+        let kind = field(
+            &architecture["registers"],
+            "architecture.registers",
+            Json::as_str,
+        )?;
+        let word = field(&architecture["word"], "architecture.word", Json::as_str)?;
+
+        let registers = match kind {
+            "letters" => RegisterFile::Letters(count as usize),
+            "numbered" => RegisterFile::Numbered(count as usize),
+            _ => return Err(SnapshotError::Field("architecture.registers")),
+        };
+        let word = match word {
+            "i32" => Word::I32,
+            "i64" => Word::I64,
+            "checked" => Word::Checked,
+            _ => return Err(SnapshotError::Field("architecture.word")),
+        };
+        let architecture = Architecture { registers, word };
+
+        let source = items(&json["instructions"], "instructions", Json::as_str)?.join("\n");
+        let mut instructions = parse_program_for(&source, architecture)
+            .map_err(SnapshotError::Instruction)?
+            .instructions;
+        for idx in items(&json["skipped"], "skipped", Json::as_u64)? {
+            let instruction = instructions
+                .get_mut(idx as usize)
+                .ok_or(SnapshotError::Field("skipped"))?;
+            *instruction = instruction.toggle();
+        }
+
+        let ip = field(&json["ip"], "ip", Json::as_u64)?;
+        let ip = usize::try_from(ip).map_err(|_| SnapshotError::Field("ip"))?;
+
+        let values = items(&json["registers"], "registers", Json::as_i64)?;
+        if values.len() != registers.len() {
+            return Err(SnapshotError::Field("registers"));
+        }
+        let mut file = Registers::new(registers);
+        for (register, value) in registers.registers().iter().zip(values) {
+            file[register] = word.wrap(value);
+        }
+
+        Ok(Self {
+            architecture,
+            ip,
+            registers: file,
+            input: items(&json["input"], "input", Json::as_i64)?.into(),
+            output: items(&json["output"], "output", Json::as_i64)?,
+            instructions,
+        })
+    }
+}
+
+fn field<'a, T>(
+    json: &'a Json,
+    name: &'static str,
+    read: impl Fn(&'a Json) -> Option<T>,
+) -> Result<T, SnapshotError> {
+    read(json).ok_or(SnapshotError::Field(name))
+}
+
+/// The items of a JSON array.
+fn items<'a, T>(
+    json: &'a Json,
+    name: &'static str,
+    read: impl Fn(&'a Json) -> Option<T>,
+) -> Result<Vec<T>, SnapshotError> {
+    field(json, name, Json::as_array)?
+        .iter()
+        .map(|item| field(item, name, &read))
+        .collect()
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SnapshotError {
+    /// The snapshot is not valid JSON.
+    Json(String),
+    /// The field is missing or has an invalid value.
+    Field(&'static str),
+    /// An instruction does not parse for the snapshot's architecture.
+    Instruction(ParseError),
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::Json(error) => write!(f, "invalid JSON: {}", error),
+            SnapshotError::Field(name) => write!(f, "missing or invalid `{}`", name),
+            SnapshotError::Instruction(error) => write!(f, "invalid instruction, {}", error),
+        }
+    }
+}
+
+impl Error for SnapshotError {}
+
+/// What a single step changed, to undo it, see [`Computer::step_back`].
+#[derive(Debug, Clone)]
+pub(super) struct Undo {
+    ip: usize,
+    registers: Registers,
+    output_len: usize,
+    /// The value read by an `in`.
+    input: Option<i64>,
+    /// The instruction changed by a `tgl`, as it was.
+    toggled: Option<(usize, Instruction)>,
+}
+
+/// The most recent steps of a [`Computer`], oldest first.
+#[derive(Debug, Clone)]
+pub(super) struct History {
+    undos: VecDeque<Undo>,
+    limit: usize,
+}
+
+impl History {
+    pub(super) fn new(limit: usize) -> Self {
+        Self {
+            undos: VecDeque::new(),
+            limit,
+        }
+    }
+
+    pub(super) fn record(&mut self, undo: Undo) {
+        if self.undos.len() == self.limit {
+            self.undos.pop_front();
+        }
+        if self.limit > 0 {
+            self.undos.push_back(undo);
+        }
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.undos.len()
+    }
+}
+
+impl Computer {
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            architecture: self.architecture,
+            ip: self.ip,
+            registers: self.registers.clone(),
+            input: self.input.clone(),
+            output: self.output.clone(),
+            instructions: self.instructions.clone(),
+        }
+    }
+
+    /// Creates a computer continuing from the snapshot, without trace or history.
+    pub fn restore(snapshot: Snapshot) -> Self {
+        let ops = snapshot.instructions.iter().map(Op::decode).collect();
+        let idioms = optimizer::detect(&snapshot.instructions);
+
+        Self {
+            registers: snapshot.registers,
+            input: snapshot.input,
+            output: snapshot.output,
+            architecture: snapshot.architecture,
+            ip: snapshot.ip,
+            instructions: snapshot.instructions,
+            ops,
+            idioms,
+            trace: None,
+            history: None,
+        }
+    }
+
+    /// Remembers the last `limit` steps from now on, so they can be undone with [`Computer::step_back`].
+    pub fn with_history(mut self, limit: usize) -> Self {
+        self.history = Some(History::new(limit));
+        self
+    }
+
+    /// The number of steps that can be undone.
+    pub fn history_len(&self) -> usize {
+        self.history.as_ref().map_or(0, History::len)
+    }
+
+    /// Undoes the last step (a whole loop idiom when it was optimized), returns `false` when there is
+    /// no step to undo. The trace, when enabled, keeps the undone step.
+    pub fn step_back(&mut self) -> bool {
+        let Some(undo) = self.history.as_mut().and_then(|h| h.undos.pop_back()) else {
+            return false;
+        };
+
+        self.ip = undo.ip;
+        self.registers = undo.registers;
+        self.output.truncate(undo.output_len);
+        if let Some(value) = undo.input {
+            self.input.push_front(value);
+        }
+        if let Some((idx, instruction)) = undo.toggled {
+            self.replace(idx, instruction);
+        }
+
+        true
+    }
+
+    /// What is needed to undo the step at `ip`, before it runs.
+    pub(super) fn undo(&self, ip: usize) -> Undo {
+        let (input, toggled) = match self.ops[ip] {
+            Op::In { .. } => (self.input.front().copied(), None),
+            Op::Toggle { register } => {
+                let toggled = self
+                    .toggle_target(ip, register)
+                    .map(|idx| (idx, self.instructions[idx].clone()));
+                (None, toggled)
+            }
+            _ => (None, None),
+        };
+
+        Undo {
+            ip,
+            registers: self.registers.clone(),
+            output_len: self.output.len(),
+            input,
+            toggled,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::super::Register;
+    use super::*;
+
+    const TOGGLES: [&str; 7] = [
+        "cpy 2 a", "tgl a", "tgl a", "tgl a", "cpy 1 a", "dec a", "dec a",
+    ];
+
+    #[test]
+    fn restores_from_json() {
+        let program = [
+            "in b", "out b", "cpy 1 a", "tgl a", "jnz 1 2", "cpy 3 a", "tgl a",
+        ];
+        let mut computer = Computer::new(&program, vec![(Register::D, -4)])
+            .unwrap()
+            .with_input([5, 6]);
+        for _ in 0..7 {
+            computer.step(true).unwrap();
+        }
+        // `jnz 1 2` got toggled into `cpy 1 2`, which is skipped
+        assert_eq!("cpy 1 2", computer.listing()[4]);
+
+        let json = computer.snapshot().to_json();
+        let restored = Computer::restore(Snapshot::from_json(&json).unwrap());
+
+        assert_eq!(computer.snapshot(), restored.snapshot());
+        assert_eq!(
+            "{\"architecture\":{\"count\":4,\"registers\":\"letters\",\"word\":\"i32\"},\
+             \"input\":[6],\"instructions\":[\"in b\",\"out b\",\"cpy 1 a\",\"tgl a\",\"jnz 1 2\",\
+             \"cpy 3 a\",\"tgl a\"],\"ip\":7,\"output\":[5],\"registers\":[3,5,0,-4],\"skipped\":[4]}",
+            json
+        );
+        assert_eq!(
+            computer.clone().run(true, 10).unwrap().registers,
+            restored.run(true, 10).unwrap().registers
+        );
+
+        // `out 7` got toggled into `inc 7`, and then into `dec 7`
+        let computer = Computer::new(&["cpy 3 a", "tgl a", "cpy 1 b", "tgl b", "out 7"], vec![])
+            .unwrap()
+            .run(false, 0)
+            .unwrap();
+        assert_eq!("dec 7", computer.listing()[4]);
+
+        let json = computer.snapshot().to_json();
+        assert!(json.contains("\"skipped\":[4,4]"));
+        assert_eq!(computer.snapshot(), Snapshot::from_json(&json).unwrap());
+    }
+
+    #[test]
+    fn reports_invalid_snapshots() {
+        let json = Computer::new(&["inc a"], vec![])
+            .unwrap()
+            .snapshot()
+            .to_json();
+
+        assert!(matches!(
+            Snapshot::from_json("{"),
+            Err(SnapshotError::Json(_))
+        ));
+        assert_eq!(
+            Err(SnapshotError::Field("registers")),
+            Snapshot::from_json(&json.replace("[0,0,0,0]", "[0,0]"))
+        );
+        assert_eq!(
+            Err(SnapshotError::Field("architecture.word")),
+            Snapshot::from_json(&json.replace("i32", "i16"))
+        );
+        assert!(matches!(
+            Snapshot::from_json(&json.replace("inc a", "inc e")),
+            Err(SnapshotError::Instruction(_))
+        ));
+    }
+
+    #[test]
+    fn steps_back_through_history() {
+        let mut computer = Computer::new(&TOGGLES, vec![]).unwrap().with_history(100);
+        let mut snapshots = vec![computer.snapshot()];
+        while !computer.is_halted() {
+            computer.step(false).unwrap();
+            snapshots.push(computer.snapshot());
+        }
+        assert_eq!(snapshots.len() - 1, computer.history_len());
+
+        while let Some(snapshot) = snapshots.pop() {
+            assert_eq!(snapshot, computer.snapshot());
+            computer.step_back();
+        }
+        assert!(!computer.step_back());
+
+        let mut computer = Computer::new(&["in a", "out a", "jnz 1 -2"], vec![])
+            .unwrap()
+            .with_input([1, 2, 3])
+            .with_history(3)
+            .run(false, 10)
+            .unwrap();
+        assert_eq!(3, computer.history_len());
+        assert!(computer.step_back() && computer.step_back() && computer.step_back());
+        assert!(!computer.step_back());
+        assert_eq!(0, computer.ip());
+        assert_eq!(vec![1, 2], computer.output);
+        assert_eq!(vec![3], computer.input.iter().copied().collect_vec());
+    }
+}
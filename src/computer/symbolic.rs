@@ -0,0 +1,763 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Not;
+
+pub use expr::Expr;
+use expr::{Ratio, Symbol};
+
+use super::optimizer::{self, Idiom};
+use super::{jump, Computer, Instruction, Register, RegisterFile, Registers, Value, Word};
+
+mod expr;
+
+/// Coefficients growing beyond this make the analysis give up, long before exact arithmetic overflows.
+const MAX_COEFFICIENT: i128 = 1 << 56;
+
+/// Closed forms for the final registers and the outputs of a program, in terms of the initial value
+/// of one register (the parameter), see [`Computer::analyze`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Analysis {
+    parameter: Register,
+    file: RegisterFile,
+    word: Word,
+    registers: Vec<Expr>,
+    output: Vec<Expr>,
+    valid_from: Option<i64>,
+}
+
+impl Analysis {
+    pub fn register(&self, register: Register) -> &Expr {
+        &self.registers[register.index()]
+    }
+
+    pub fn output(&self) -> &[Expr] {
+        &self.output
+    }
+
+    /// The smallest parameter value the closed forms hold for, `None` when they hold for any value.
+    pub fn valid_from(&self) -> Option<i64> {
+        self.valid_from
+    }
+
+    /// The final registers for the given parameter value, without running the program.
+    ///
+    /// Returns `None` when the closed forms don't hold for the value, or a register doesn't fit the word.
+    pub fn evaluate(&self, value: i64) -> Option<Registers> {
+        if self.valid_from.is_some_and(|from| value < from) {
+            return None;
+        }
+
+        let mut registers = Registers::new(self.file);
+        for register in self.file.registers() {
+            let exact = self
+                .register(register)
+                .evaluate(Symbol::Initial(self.parameter), value as i128)?
+                .to_integer()?;
+            registers[&register] = i64::try_from(exact)
+                .ok()
+                .filter(|v| (self.word.min()..=self.word.max()).contains(v))?;
+        }
+
+        Some(registers)
+    }
+}
+
+/// One line per register, then the outputs (if any) and the parameter values the result holds for.
+impl Display for Analysis {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for register in self.file.registers() {
+            writeln!(f, "{} = {}", register, self.register(register))?;
+        }
+        if self.output.is_empty().not() {
+            let output = self
+                .output
+                .iter()
+                .map(|o| o.to_string())
+                .collect::<Vec<_>>();
+            writeln!(f, "out: {}", output.join(", "))?;
+        }
+
+        let parameter = Symbol::Initial(self.parameter);
+        match self.valid_from {
+            Some(from) => writeln!(f, "valid for {} >= {}", parameter, from),
+            None => writeln!(f, "valid for any {}", parameter),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SymbolicError {
+    /// The `jnz` at `ip` jumps by an offset depending on the parameter.
+    SymbolicOffset { ip: usize },
+    /// The sign of `value` at `ip` is not known, even for large parameter values.
+    Undecidable { ip: usize, value: String },
+    /// The coefficients of the closed forms grew too large at `ip`.
+    Overflow { ip: usize },
+    /// The `in` at `ip` reads a value that is not known.
+    Input { ip: usize },
+    /// The program did not halt within the step limit, e.g. because a loop could not be summarised.
+    StepLimit,
+}
+
+impl Display for SymbolicError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SymbolicError::SymbolicOffset { ip } => {
+                write!(f, "jump offset at {} depends on the parameter", ip)
+            }
+            SymbolicError::Undecidable { ip, value } => {
+                write!(f, "can't decide the sign of `{}` at {}", value, ip)
+            }
+            SymbolicError::Overflow { ip } => write!(f, "coefficients too large at {}", ip),
+            SymbolicError::Input { ip } => write!(f, "reads input at {}", ip),
+            SymbolicError::StepLimit => write!(f, "step limit reached"),
+        }
+    }
+}
+
+impl Error for SymbolicError {}
+
+impl Computer {
+    /// Executes the program from its current state over a symbolic value for `parameter`,
+    /// returning closed forms (in exact arithmetic) for the registers once it halts.
+    ///
+    /// Every branch is taken the way it goes for large parameter values, which is then recorded as
+    /// a lower bound on the parameter. Loops counting a register down one at a time are summarised
+    /// instead of executed, yielding e.g. `a = a0! + 6853` for the Day 23 program.
+    pub fn analyze(
+        &self,
+        parameter: Register,
+        max_steps: usize,
+    ) -> Result<Analysis, SymbolicError> {
+        let symbol = Symbol::Initial(parameter);
+        let file = self.architecture.registers;
+        let registers = file
+            .registers()
+            .into_iter()
+            .map(|r| match r == parameter {
+                true => Expr::symbol(symbol),
+                false => Expr::constant(self.registers[&r] as i128),
+            })
+            .collect();
+
+        let mut executor = Executor {
+            parameter: symbol,
+            file,
+            registers,
+            output: vec![],
+            ip: self.ip,
+            instructions: self.instructions.clone(),
+            idioms: self.idioms.clone(),
+            valid_from: i128::MIN,
+            path: vec![],
+            last_visit: vec![None; self.instructions.len()],
+        };
+
+        for _ in 0..max_steps {
+            if executor.ip >= executor.instructions.len() {
+                let valid_from = (executor.valid_from > i64::MIN as i128)
+                    .then(|| executor.valid_from.min(i64::MAX as i128) as i64);
+
+                return Ok(Analysis {
+                    parameter,
+                    file,
+                    word: self.architecture.word,
+                    registers: executor.registers,
+                    output: executor.output,
+                    valid_from,
+                });
+            }
+
+            executor.step()?;
+        }
+
+        Err(SymbolicError::StepLimit)
+    }
+}
+
+/// What happened at one step, enough to replay it on other register values.
+#[derive(Debug, Copy, Clone)]
+enum Step {
+    /// The idiom at `ip` got fused.
+    Fused { ip: usize },
+    /// The instruction at `ip` got executed. For a `jnz` the sign of its value, for a `tgl` the side
+    /// of the program its target is on.
+    Executed { ip: usize, sign: i128 },
+    /// A step that changes more than the registers, or that can't be replayed.
+    Opaque,
+}
+
+/// Condition on the registers for a loop iteration to take the same path again.
+#[derive(Debug, Clone)]
+enum Condition {
+    Positive(Expr),
+    Zero(Expr),
+}
+
+/// How a register changes from one loop iteration to the next.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Recurrence {
+    /// `r -= 1`, the register counting the iterations.
+    Counter,
+    Invariant,
+    /// Set to the same constant every iteration.
+    Reset,
+    /// `x += c`
+    Sum(Ratio),
+    /// `x *= counter + c`
+    Product(i128),
+}
+
+struct Executor {
+    parameter: Symbol,
+    file: RegisterFile,
+    registers: Vec<Expr>,
+    output: Vec<Expr>,
+    ip: usize,
+    instructions: Vec<Instruction>,
+    idioms: Vec<Option<Idiom>>,
+    /// The parameter has to be at least this large for the decisions taken so far.
+    valid_from: i128,
+    path: Vec<Step>,
+    /// Index into `path` of the last step executed at each instruction.
+    last_visit: Vec<Option<usize>>,
+}
+
+impl Executor {
+    fn step(&mut self) -> Result<(), SymbolicError> {
+        let ip = self.ip;
+        self.last_visit[ip] = Some(self.path.len());
+
+        if let Some(idiom) = self.idioms[ip] {
+            let counts = iteration_counts(&idiom, &self.registers);
+            let mut signs = vec![];
+            for count in &counts {
+                signs.push(self.sign(count, ip)?);
+            }
+
+            if signs.iter().all(|sign| *sign > 0) {
+                for count in counts {
+                    self.assume(count, ip)?;
+                }
+                fuse(&idiom, &mut self.registers);
+                self.path.push(Step::Fused { ip });
+                self.ip = ip + idiom.len();
+                return self.check_size(ip);
+            }
+            self.path.push(Step::Opaque);
+        }
+
+        self.ip = ip + 1;
+        match self.instructions[ip].clone() {
+            Instruction::Jnz {
+                value,
+                offset,
+                valid: true,
+            } => {
+                let value = self.value(value);
+                let sign = self.sign(&value, ip)?;
+                self.assume(value.scale(Ratio::integer(sign)), ip)?;
+                self.path.push(Step::Executed { ip, sign });
+
+                if sign != 0 {
+                    let offset = self
+                        .value(offset)
+                        .as_integer()
+                        .and_then(|o| i64::try_from(o).ok())
+                        .ok_or(SymbolicError::SymbolicOffset { ip })?;
+                    self.ip = jump(ip, offset);
+
+                    if self.ip <= ip {
+                        self.accelerate(self.ip)?;
+                    }
+                }
+            }
+            Instruction::Tgl { register } => {
+                let target = &Expr::constant(ip as i128) + &self.registers[register.index()];
+
+                match target.as_integer().map(usize::try_from) {
+                    Some(Ok(idx)) if idx < self.instructions.len() => {
+                        self.instructions[idx] = self.instructions[idx].toggle();
+                        optimizer::refresh(&mut self.idioms, &self.instructions, idx);
+                        self.path.push(Step::Opaque);
+                    }
+                    _ => {
+                        let side = self.sign(&target, ip)?;
+                        self.assume(outside(&target, side, self.instructions.len()), ip)?;
+                        self.path.push(Step::Executed { ip, sign: side });
+                    }
+                }
+            }
+            Instruction::In { .. } => return Err(SymbolicError::Input { ip }),
+            Instruction::Out { value } => {
+                self.output.push(self.value(value));
+                self.path.push(Step::Opaque);
+            }
+            instruction => {
+                assign(&instruction, &mut self.registers);
+                self.path.push(Step::Executed { ip, sign: 0 });
+            }
+        }
+
+        self.check_size(ip)
+    }
+
+    fn value(&self, value: Value) -> Expr {
+        symbolic_value(value, &self.registers)
+    }
+
+    /// The sign of the value, for large enough parameter values.
+    fn sign(&self, value: &Expr, ip: usize) -> Result<i128, SymbolicError> {
+        value
+            .asymptotic_sign(self.parameter)
+            .ok_or_else(|| SymbolicError::Undecidable {
+                ip,
+                value: value.to_string(),
+            })
+    }
+
+    /// Restricts the parameter to the values making `value` positive (or zero when it is zero).
+    fn assume(&mut self, value: Expr, ip: usize) -> Result<(), SymbolicError> {
+        if value.is_zero() {
+            return Ok(());
+        }
+
+        let from =
+            value
+                .positive_from(self.parameter)
+                .ok_or_else(|| SymbolicError::Undecidable {
+                    ip,
+                    value: value.to_string(),
+                })?;
+        self.valid_from = self.valid_from.max(from);
+
+        Ok(())
+    }
+
+    fn check_size(&self, ip: usize) -> Result<(), SymbolicError> {
+        let fits = self
+            .registers
+            .iter()
+            .chain(&self.output)
+            .all(|e| e.fits(MAX_COEFFICIENT));
+
+        fits.then_some(()).ok_or(SymbolicError::Overflow { ip })
+    }
+
+    /// Summarises the loop that just got back to `head`, when its last iteration shows one register
+    /// counting down while the others follow a recurrence with a closed form.
+    ///
+    /// The loop is fast-forwarded up to the last iteration for which the path through it is known
+    /// to stay the same, the remaining iterations are executed normally.
+    fn accelerate(&mut self, head: usize) -> Result<(), SymbolicError> {
+        let Some(start) = self.last_visit[head] else {
+            return Ok(());
+        };
+        let Some((after, conditions)) = self.replay(head, &self.path[start..]) else {
+            return Ok(());
+        };
+        let Some(recurrences) = self.recurrences(&after) else {
+            return Ok(());
+        };
+        let Some(last) = self.last_counter(&recurrences, &conditions) else {
+            return Ok(());
+        };
+
+        let counter = recurrences
+            .iter()
+            .position(|r| *r == Recurrence::Counter)
+            .unwrap();
+        let first = self.registers[counter].clone();
+        let iterations = &first - &Expr::constant(last);
+        if self.sign(&iterations, head)? <= 0 {
+            return Ok(());
+        }
+
+        let mut registers = self.registers.clone();
+        let mut assumptions = vec![&iterations + &Expr::constant(1)];
+        for (register, recurrence) in recurrences.iter().enumerate() {
+            registers[register] = match *recurrence {
+                Recurrence::Counter => Expr::constant(last),
+                Recurrence::Invariant | Recurrence::Reset => continue,
+                Recurrence::Sum(step) => &self.registers[register] + &iterations.scale(step),
+                Recurrence::Product(offset) => {
+                    // Multiplied by `first + offset` down to `last + 1 + offset`
+                    let top = &first + &Expr::constant(offset);
+                    let Some(factorial) = self.factorial_ratio(&top, last + offset) else {
+                        return Ok(());
+                    };
+                    if self.sign(&self.registers[register], head)? <= 0 {
+                        return Ok(());
+                    }
+                    assumptions.push(self.registers[register].clone());
+
+                    &self.registers[register] * &factorial
+                }
+            };
+        }
+
+        for assumption in assumptions {
+            self.assume(assumption, head)?;
+        }
+        self.registers = registers;
+        self.path.push(Step::Opaque);
+
+        Ok(())
+    }
+
+    /// `top! / bottom!`, when `top` is the parameter plus a constant.
+    fn factorial_ratio(&self, top: &Expr, bottom: i128) -> Option<Expr> {
+        let [shift, one] = top.polynomial(self.parameter)?[..] else {
+            return None;
+        };
+        if one != Ratio::integer(1) {
+            return None;
+        }
+
+        let divisor = expr::factorial(bottom)?;
+        Some(Expr::factorial(self.parameter, shift.to_integer()?).scale(Ratio::new(1, divisor)))
+    }
+
+    /// Replays one loop iteration on the symbolic values the registers have at its start (`head`),
+    /// returning the registers at its end and the conditions for the next iteration to go the same way.
+    fn replay(&self, head: usize, steps: &[Step]) -> Option<(Vec<Expr>, Vec<Condition>)> {
+        let mut registers = self
+            .file
+            .registers()
+            .into_iter()
+            .map(|r| Expr::symbol(Symbol::Head(r)))
+            .collect::<Vec<_>>();
+        let mut conditions = vec![];
+
+        for (idx, step) in steps.iter().enumerate() {
+            match *step {
+                Step::Opaque => return None,
+                Step::Fused { ip } => {
+                    let idiom = self.idioms[ip]?;
+                    for count in iteration_counts(&idiom, &registers) {
+                        conditions.push(Condition::Positive(count));
+                    }
+                    fuse(&idiom, &mut registers);
+                }
+                Step::Executed { ip, sign } => match self.instructions[ip] {
+                    Instruction::Jnz {
+                        value,
+                        offset,
+                        valid: true,
+                    } => {
+                        let value = symbolic_value(value, &registers);
+                        conditions.push(match sign {
+                            0 => Condition::Zero(value),
+                            sign => Condition::Positive(value.scale(Ratio::integer(sign))),
+                        });
+
+                        if sign != 0 {
+                            let next = match steps.get(idx + 1) {
+                                Some(Step::Fused { ip } | Step::Executed { ip, .. }) => *ip,
+                                Some(Step::Opaque) => return None,
+                                None => head,
+                            };
+                            let expected = Expr::constant(next as i128 - ip as i128);
+                            let offset = symbolic_value(offset, &registers);
+                            conditions.push(Condition::Zero(&offset - &expected));
+                        }
+                    }
+                    Instruction::Tgl { register } => {
+                        let target = &Expr::constant(ip as i128) + &registers[register.index()];
+                        conditions.push(Condition::Positive(outside(
+                            &target,
+                            sign,
+                            self.instructions.len(),
+                        )));
+                    }
+                    ref instruction => assign(instruction, &mut registers),
+                },
+            }
+        }
+
+        Some((registers, conditions))
+    }
+
+    /// Classifies how each register changes over an iteration, see [`Recurrence`].
+    fn recurrences(&self, after: &[Expr]) -> Option<Vec<Recurrence>> {
+        let counter = self.file.registers().into_iter().find(|r| {
+            let change = &after[r.index()] - &Expr::symbol(Symbol::Head(*r));
+            change.as_integer() == Some(-1) && self.registers[r.index()].as_constant().is_none()
+        })?;
+
+        let mut recurrences = vec![Recurrence::Invariant; self.file.len()];
+        for register in self.file.registers() {
+            let idx = register.index();
+            let before = Expr::symbol(Symbol::Head(register));
+            let change = &after[idx] - &before;
+
+            recurrences[idx] = if register == counter {
+                Recurrence::Counter
+            } else if change.is_zero() {
+                Recurrence::Invariant
+            } else if after[idx].symbols().is_empty() {
+                // Only a constant that is already in place stays the same over all iterations
+                (after[idx] == self.registers[idx]).then_some(Recurrence::Reset)?
+            } else if let Some(step) = change.as_constant() {
+                Recurrence::Sum(step)
+            } else {
+                let factor = after[idx].quotient(Symbol::Head(register))?;
+                let offset = &factor - &Expr::symbol(Symbol::Head(counter));
+                Recurrence::Product(offset.as_integer()?)
+            };
+        }
+
+        Some(recurrences)
+    }
+
+    /// The lowest value of the counter the loop can be fast-forwarded to: every iteration starting
+    /// with a higher counter value is known to take the same path.
+    fn last_counter(&self, recurrences: &[Recurrence], conditions: &[Condition]) -> Option<i128> {
+        let counter = recurrences.iter().position(|r| *r == Recurrence::Counter)?;
+        let counter = Symbol::Head(self.file.registers()[counter]);
+
+        // Invariant and reset registers keep their current value
+        let values = |symbol: Symbol| {
+            let Symbol::Head(register) = symbol else {
+                return None;
+            };
+            match recurrences[register.index()] {
+                Recurrence::Invariant | Recurrence::Reset => {
+                    Some(self.registers[register.index()].clone())
+                }
+                _ => None,
+            }
+        };
+
+        let mut last = None;
+        // Factorials need a non-negative bottom
+        let mut floor = recurrences
+            .iter()
+            .filter_map(|r| match r {
+                Recurrence::Product(offset) => Some(-offset),
+                _ => None,
+            })
+            .fold(i128::MIN, i128::max);
+
+        for condition in conditions {
+            match condition {
+                Condition::Zero(value) => {
+                    value.substitute(&values)?.is_zero().then_some(())?;
+                }
+                Condition::Positive(value) => {
+                    let value = value.substitute(&values)?;
+                    let symbols = value.symbols().into_iter().collect::<Vec<_>>();
+
+                    match symbols[..] {
+                        [] => (value.as_integer()? > 0).then_some(())?,
+                        [symbol] if symbol == counter => {
+                            let from = value.positive_from(counter)?;
+                            last = last.max(Some(from.saturating_sub(1)));
+                        }
+                        // A product stays positive as long as all of its factors are
+                        [Symbol::Head(register)] => {
+                            let Recurrence::Product(offset) = recurrences[register.index()] else {
+                                return None;
+                            };
+                            let factor = value.quotient(Symbol::Head(register))?.as_constant()?;
+                            (factor.signum() > 0).then_some(())?;
+                            floor = floor.max(-offset);
+                        }
+                        _ => return None,
+                    }
+                }
+            }
+        }
+
+        last.map(|last: i128| last.max(floor))
+            .filter(|last| *last > i128::MIN)
+    }
+}
+
+fn symbolic_value(value: Value, registers: &[Expr]) -> Expr {
+    match value {
+        Value::R { register } => registers[register.index()].clone(),
+        Value::C { constant } => Expr::constant(constant as i128),
+    }
+}
+
+/// Executes a `cpy`, `inc` or `dec` (anything else does not change the registers).
+fn assign(instruction: &Instruction, registers: &mut [Expr]) {
+    match *instruction {
+        Instruction::Cpy { from, to } => registers[to.index()] = symbolic_value(from, registers),
+        Instruction::Inc { register } => {
+            registers[register.index()] = &registers[register.index()] + &Expr::constant(1)
+        }
+        Instruction::Dec { register } => {
+            registers[register.index()] = &registers[register.index()] - &Expr::constant(1)
+        }
+        _ => {}
+    }
+}
+
+/// The number of iterations of each loop in the idiom, see [`Idiom::apply`].
+fn iteration_counts(idiom: &Idiom, registers: &[Expr]) -> Vec<Expr> {
+    let count = |start: &Expr, step: i64| start.scale(Ratio::integer(-step as i128));
+
+    match *idiom {
+        Idiom::Add { counter, step, .. } => vec![count(&registers[counter.index()], step)],
+        Idiom::Mul {
+            source,
+            inner_step,
+            outer,
+            outer_step,
+            ..
+        } => vec![
+            count(&symbolic_value(source, registers), inner_step),
+            count(&registers[outer.index()], outer_step),
+        ],
+    }
+}
+
+/// Applies the idiom, assuming its iteration counts are positive.
+fn fuse(idiom: &Idiom, registers: &mut [Expr]) {
+    let counts = iteration_counts(idiom, registers);
+
+    let (target, delta, cleared) = match *idiom {
+        Idiom::Add {
+            target,
+            delta,
+            counter,
+            ..
+        } => (target, delta, vec![counter]),
+        Idiom::Mul {
+            target,
+            delta,
+            inner,
+            outer,
+            ..
+        } => (target, delta, vec![inner, outer]),
+    };
+
+    let product = counts
+        .iter()
+        .fold(Expr::constant(delta as i128), |product, count| {
+            &product * count
+        });
+    registers[target.index()] = &registers[target.index()] + &product;
+    cleared
+        .into_iter()
+        .for_each(|r| registers[r.index()] = Expr::constant(0));
+}
+
+/// Value that is positive when a `tgl` target stays on the given side of the program:
+/// before its start (negative side) or after its end.
+fn outside(target: &Expr, side: i128, len: usize) -> Expr {
+    match side {
+        side if side < 0 => -target,
+        _ => target - &Expr::constant(len as i128 - 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Computer, Register};
+    use super::*;
+
+    const DAY_23: [&str; 26] = [
+        "cpy a b",
+        "dec b",
+        "cpy a d",
+        "cpy 0 a",
+        "cpy b c",
+        "inc a",
+        "dec c",
+        "jnz c -2",
+        "dec d",
+        "jnz d -5",
+        "dec b",
+        "cpy b c",
+        "cpy c d",
+        "dec d",
+        "inc c",
+        "jnz d -2",
+        "tgl c",
+        "cpy -16 c",
+        "jnz 1 c",
+        "cpy 89 c",
+        "jnz 77 d",
+        "inc a",
+        "inc d",
+        "jnz d -2",
+        "inc c",
+        "jnz c -5",
+    ];
+
+    fn analyze(code: &[&str], parameter: Register) -> Result<Analysis, SymbolicError> {
+        Computer::new(code, vec![])
+            .unwrap()
+            .analyze(parameter, 10_000)
+    }
+
+    #[test]
+    fn finds_factorial_for_day_23() {
+        let analysis = analyze(&DAY_23, Register::A).unwrap();
+
+        assert_eq!("a0! + 6853", analysis.register(Register::A).to_string());
+        assert_eq!(Some(7), analysis.valid_from());
+        assert_eq!(11_893, analysis.evaluate(7).unwrap()[&Register::A]);
+        assert_eq!(479_008_453, analysis.evaluate(12).unwrap()[&Register::A]);
+        assert_eq!(None, analysis.evaluate(6));
+    }
+
+    #[test]
+    fn finds_polynomials() {
+        let square = [
+            "cpy a b", "cpy a d", "cpy 0 a", "cpy b c", "inc a", "dec c", "jnz c -2", "dec d",
+            "jnz d -5", "inc a", "inc a", "inc a",
+        ];
+        let analysis = analyze(&square, Register::A).unwrap();
+
+        assert_eq!(
+            "a = a0^2 + 3\nb = a0\nc = 0\nd = 0\nvalid for a0 >= 1\n",
+            analysis.to_string()
+        );
+    }
+
+    #[test]
+    fn summarises_counting_loops() {
+        let triple = [
+            "cpy a b", "cpy 0 a", "inc a", "inc a", "inc a", "dec b", "jnz b -4",
+        ];
+        let analysis = analyze(&triple, Register::A).unwrap();
+
+        assert_eq!("3*a0", analysis.register(Register::A).to_string());
+        assert_eq!(Some(2), analysis.valid_from());
+        assert_eq!(
+            Computer::new(&triple, vec![(Register::A, 40)])
+                .unwrap()
+                .run(false, 0)
+                .unwrap()
+                .registers,
+            analysis.evaluate(40).unwrap()
+        );
+    }
+
+    #[test]
+    fn reports_outputs_and_errors() {
+        let analysis = analyze(&["out a", "inc a", "out a"], Register::A).unwrap();
+
+        assert_eq!(
+            vec!["a0", "a0 + 1"],
+            analysis
+                .output()
+                .iter()
+                .map(|o| o.to_string())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(None, analysis.valid_from());
+        assert_eq!(
+            Err(SymbolicError::SymbolicOffset { ip: 0 }),
+            analyze(&["jnz 1 a"], Register::A)
+        );
+        assert_eq!(
+            Err(SymbolicError::StepLimit),
+            analyze(&["inc a", "jnz 1 -1"], Register::A)
+        );
+    }
+}
@@ -0,0 +1,618 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Mul, Neg, Sub};
+
+use super::super::Register;
+
+/// Bound on the values scanned when looking for the last root of a polynomial.
+const MAX_SCAN: f64 = 1_000_000.0;
+
+/// An exact fraction, always stored in lowest terms with a positive denominator.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Ratio {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Ratio {
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+
+        Self {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        }
+    }
+
+    pub fn integer(value: i128) -> Self {
+        Self::new(value, 1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    pub fn signum(&self) -> i128 {
+        self.numerator.signum()
+    }
+
+    pub fn to_integer(self) -> Option<i128> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+
+    /// Whether both numerator and denominator stay below `limit` in absolute value.
+    pub fn fits(&self, limit: i128) -> bool {
+        self.numerator.abs() < limit && self.denominator < limit
+    }
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        let numerator = self
+            .numerator
+            .checked_mul(other.denominator)?
+            .checked_add(other.numerator.checked_mul(self.denominator)?)?;
+
+        Some(Self::new(
+            numerator,
+            self.denominator.checked_mul(other.denominator)?,
+        ))
+    }
+
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        Some(Self::new(
+            self.numerator.checked_mul(other.numerator)?,
+            self.denominator.checked_mul(other.denominator)?,
+        ))
+    }
+
+    fn as_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+impl Add for Ratio {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other).expect("fraction overflow")
+    }
+}
+
+impl Mul for Ratio {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.checked_mul(other).expect("fraction overflow")
+    }
+}
+
+impl Neg for Ratio {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.numerator, self.denominator)
+    }
+}
+
+impl Display for Ratio {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.denominator {
+            1 => write!(f, "{}", self.numerator),
+            denominator => write!(f, "{}/{}", self.numerator, denominator),
+        }
+    }
+}
+
+/// What an expression can depend on.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Symbol {
+    /// The initial value of a register, shown as e.g. `a0`.
+    Initial(Register),
+    /// The value of a register at the start of a loop iteration, while summarising that loop.
+    Head(Register),
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            // `r1_0` rather than `r10` for numbered registers
+            Symbol::Initial(register @ Register::Numbered(_)) => write!(f, "{}_0", register),
+            Symbol::Initial(register) => write!(f, "{}0", register),
+            Symbol::Head(register) => write!(f, "{}'", register),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+enum Atom {
+    Power(Symbol),
+    /// `(symbol + shift)!`
+    Factorial(Symbol, i128),
+}
+
+/// Product of atoms, each raised to its (positive) exponent.
+type Monomial = BTreeMap<Atom, u32>;
+
+/// Exact closed-form value: a sum of monomials over symbols and factorials of symbols, with
+/// fractional coefficients.
+///
+/// Kept in a canonical form, where a factorial is never multiplied by its own symbol:
+/// `n * (n + s)!` is rewritten into `(n + s + 1)! - (s + 1) * (n + s)!`, so that e.g.
+/// `n * (n - 1)!` becomes `n!`.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Expr {
+    terms: BTreeMap<Monomial, Ratio>,
+}
+
+impl Expr {
+    pub fn constant(value: i128) -> Self {
+        Self::ratio(Ratio::integer(value))
+    }
+
+    pub fn ratio(value: Ratio) -> Self {
+        let mut expr = Self::default();
+        expr.add_term(Monomial::new(), value);
+        expr
+    }
+
+    pub fn symbol(symbol: Symbol) -> Self {
+        let mut expr = Self::default();
+        expr.add_term(
+            Monomial::from([(Atom::Power(symbol), 1)]),
+            Ratio::integer(1),
+        );
+        expr
+    }
+
+    /// `(symbol + shift)!`
+    pub fn factorial(symbol: Symbol, shift: i128) -> Self {
+        let mut expr = Self::default();
+        expr.add_term(
+            Monomial::from([(Atom::Factorial(symbol, shift), 1)]),
+            Ratio::integer(1),
+        );
+        expr
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn as_constant(&self) -> Option<Ratio> {
+        match self.terms.iter().collect::<Vec<_>>()[..] {
+            [] => Some(Ratio::integer(0)),
+            [(monomial, coefficient)] if monomial.is_empty() => Some(*coefficient),
+            _ => None,
+        }
+    }
+
+    pub fn as_integer(&self) -> Option<i128> {
+        self.as_constant().and_then(Ratio::to_integer)
+    }
+
+    pub fn symbols(&self) -> BTreeSet<Symbol> {
+        self.terms
+            .keys()
+            .flat_map(|m| m.keys())
+            .map(|atom| match *atom {
+                Atom::Power(symbol) | Atom::Factorial(symbol, _) => symbol,
+            })
+            .collect()
+    }
+
+    /// Whether every coefficient stays below `limit` in absolute value, see [`Ratio::fits`].
+    pub fn fits(&self, limit: i128) -> bool {
+        self.terms.values().all(|c| c.fits(limit))
+    }
+
+    pub fn scale(&self, factor: Ratio) -> Self {
+        let mut expr = Self::default();
+        for (monomial, coefficient) in &self.terms {
+            expr.add_term(monomial.clone(), *coefficient * factor);
+        }
+        expr
+    }
+
+    /// The coefficients (lowest power first) when this is a polynomial in nothing but `symbol`.
+    pub fn polynomial(&self, symbol: Symbol) -> Option<Vec<Ratio>> {
+        let mut coefficients = vec![];
+
+        for (monomial, coefficient) in &self.terms {
+            let power = match monomial.iter().collect::<Vec<_>>()[..] {
+                [] => 0,
+                [(Atom::Power(s), power)] if *s == symbol => *power as usize,
+                _ => return None,
+            };
+            if coefficients.len() <= power {
+                coefficients.resize(power + 1, Ratio::integer(0));
+            }
+            coefficients[power] = *coefficient;
+        }
+
+        Some(coefficients)
+    }
+
+    /// Divides by `symbol`, when every monomial contains it exactly once.
+    pub fn quotient(&self, symbol: Symbol) -> Option<Self> {
+        let mut expr = Self::default();
+
+        for (monomial, coefficient) in &self.terms {
+            let mut monomial = monomial.clone();
+            if monomial.remove(&Atom::Power(symbol)) != Some(1) {
+                return None;
+            }
+            expr.add_term(monomial, *coefficient);
+        }
+
+        Some(expr)
+    }
+
+    /// Replaces symbols by the expressions `values` returns for them (keeping those it returns `None` for).
+    ///
+    /// Fails when a factorial's symbol is replaced by something other than a constant or a symbol plus a constant.
+    pub fn substitute(&self, values: &impl Fn(Symbol) -> Option<Expr>) -> Option<Self> {
+        let mut expr = Self::default();
+
+        for (monomial, coefficient) in &self.terms {
+            let mut product = Self::ratio(*coefficient);
+
+            for (atom, exponent) in monomial {
+                let factor = match *atom {
+                    Atom::Power(symbol) => values(symbol).unwrap_or_else(|| Self::symbol(symbol)),
+                    Atom::Factorial(symbol, shift) => match values(symbol) {
+                        None => Self::factorial(symbol, shift),
+                        Some(value) => value.factorial_of(shift)?,
+                    },
+                };
+                for _ in 0..*exponent {
+                    product = &product * &factor;
+                }
+            }
+
+            expr = &expr + &product;
+        }
+
+        Some(expr)
+    }
+
+    /// `(self + shift)!`, when `self` is a constant or a symbol plus a constant.
+    fn factorial_of(&self, shift: i128) -> Option<Self> {
+        if let Some(value) = self.as_integer() {
+            return factorial(value.checked_add(shift)?).map(Self::constant);
+        }
+
+        let [symbol] = self.symbols().into_iter().collect::<Vec<_>>()[..] else {
+            return None;
+        };
+        match self.polynomial(symbol)?[..] {
+            [constant, one] if one == Ratio::integer(1) => {
+                Some(Self::factorial(symbol, constant.to_integer()? + shift))
+            }
+            _ => None,
+        }
+    }
+
+    /// The exact value when `symbol`, the only symbol in the expression, equals `value`.
+    pub fn evaluate(&self, symbol: Symbol, value: i128) -> Option<Ratio> {
+        self.terms
+            .iter()
+            .try_fold(Ratio::integer(0), |sum, (monomial, coefficient)| {
+                let term =
+                    monomial
+                        .iter()
+                        .try_fold(*coefficient, |product, (atom, exponent)| {
+                            let base = match *atom {
+                                Atom::Power(s) if s == symbol => value,
+                                Atom::Factorial(s, shift) if s == symbol => {
+                                    factorial(value.checked_add(shift)?)?
+                                }
+                                _ => return None,
+                            };
+                            product.checked_mul(Ratio::integer(base.checked_pow(*exponent)?))
+                        })?;
+                sum.checked_add(term)
+            })
+    }
+
+    /// The sign the expression takes for large enough values of `symbol`, its only symbol.
+    pub fn asymptotic_sign(&self, symbol: Symbol) -> Option<i128> {
+        if let Some(constant) = self.as_constant() {
+            return Some(constant.signum());
+        }
+
+        // In canonical form factorials grow faster than any other monomial, the highest shift first
+        let mut dominant = None;
+        for (monomial, coefficient) in &self.terms {
+            let rank = match monomial.iter().collect::<Vec<_>>()[..] {
+                [] => (0, 0),
+                [(Atom::Power(s), power)] if *s == symbol => (0, *power as i128),
+                [(Atom::Factorial(s, shift), 1)] if *s == symbol => (1, *shift),
+                _ => return None,
+            };
+            if dominant.is_none_or(|(best, _)| rank > best) {
+                dominant = Some((rank, coefficient.signum()));
+            }
+        }
+
+        dominant.map(|(_, sign)| sign)
+    }
+
+    /// The smallest `n` such that the expression is positive for every integer value of `symbol`
+    /// (its only symbol) from `n` on, `i128::MIN` when it is positive everywhere.
+    ///
+    /// Returns `None` when it is not positive for large values, or when that can't be established.
+    pub fn positive_from(&self, symbol: Symbol) -> Option<i128> {
+        if let Some(constant) = self.as_constant() {
+            return (constant.signum() > 0).then_some(i128::MIN);
+        }
+
+        match self.polynomial(symbol) {
+            Some(coefficients) => positive_polynomial_from(&coefficients),
+            None => self.positive_terms_from(symbol),
+        }
+    }
+
+    /// When every coefficient is positive, the expression is positive wherever all its atoms are.
+    fn positive_terms_from(&self, symbol: Symbol) -> Option<i128> {
+        let mut from = i128::MIN;
+
+        for (monomial, coefficient) in &self.terms {
+            if coefficient.signum() <= 0 {
+                return None;
+            }
+            for atom in monomial.keys() {
+                from = from.max(match *atom {
+                    Atom::Power(s) if s == symbol => 1,
+                    Atom::Factorial(s, shift) if s == symbol => -shift,
+                    _ => return None,
+                });
+            }
+        }
+
+        Some(from)
+    }
+
+    fn add_term(&mut self, monomial: Monomial, coefficient: Ratio) {
+        let sum = coefficient
+            + self
+                .terms
+                .get(&monomial)
+                .copied()
+                .unwrap_or(Ratio::integer(0));
+
+        if sum.is_zero() {
+            self.terms.remove(&monomial);
+        } else {
+            self.terms.insert(monomial, sum);
+        }
+    }
+
+    /// Adds the monomial after rewriting it into canonical form.
+    fn add_normalized(&mut self, mut monomial: Monomial, coefficient: Ratio) {
+        let rewrite = monomial.iter().find_map(|(atom, exponent)| match *atom {
+            Atom::Factorial(symbol, shift)
+                if *exponent == 1 && monomial.contains_key(&Atom::Power(symbol)) =>
+            {
+                let factorials = monomial
+                    .keys()
+                    .filter(|a| matches!(a, Atom::Factorial(s, _) if *s == symbol))
+                    .count();
+                (factorials == 1).then_some((symbol, shift))
+            }
+            _ => None,
+        });
+
+        let Some((symbol, shift)) = rewrite else {
+            self.add_term(monomial, coefficient);
+            return;
+        };
+
+        // n * (n + s)! = (n + s + 1)! - (s + 1) * (n + s)!
+        let power = Atom::Power(symbol);
+        match monomial[&power] {
+            1 => monomial.remove(&power),
+            exponent => monomial.insert(power, exponent - 1),
+        };
+        let mut higher = monomial.clone();
+        higher.remove(&Atom::Factorial(symbol, shift));
+        higher.insert(Atom::Factorial(symbol, shift + 1), 1);
+
+        self.add_normalized(higher, coefficient);
+        self.add_normalized(monomial, -coefficient * Ratio::integer(shift + 1));
+    }
+}
+
+impl Add for &Expr {
+    type Output = Expr;
+
+    fn add(self, other: &Expr) -> Expr {
+        let mut expr = self.clone();
+        for (monomial, coefficient) in &other.terms {
+            expr.add_term(monomial.clone(), *coefficient);
+        }
+        expr
+    }
+}
+
+impl Sub for &Expr {
+    type Output = Expr;
+
+    fn sub(self, other: &Expr) -> Expr {
+        self + &-other
+    }
+}
+
+impl Neg for &Expr {
+    type Output = Expr;
+
+    fn neg(self) -> Expr {
+        self.scale(Ratio::integer(-1))
+    }
+}
+
+impl Mul for &Expr {
+    type Output = Expr;
+
+    fn mul(self, other: &Expr) -> Expr {
+        let mut expr = Expr::default();
+
+        for (left, left_coefficient) in &self.terms {
+            for (right, right_coefficient) in &other.terms {
+                let mut monomial = left.clone();
+                for (atom, exponent) in right {
+                    *monomial.entry(*atom).or_default() += exponent;
+                }
+                expr.add_normalized(monomial, *left_coefficient * *right_coefficient);
+            }
+        }
+
+        expr
+    }
+}
+
+/// Readable formula with the fastest growing monomials first, e.g. `a0! + 3*a0^2 - 1/2*a0 + 6853`.
+impl Display for Expr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        let mut terms = self.terms.iter().collect::<Vec<_>>();
+        terms.sort_by_key(|(monomial, _)| {
+            let shift = monomial.keys().find_map(|a| match a {
+                Atom::Factorial(_, shift) => Some(*shift),
+                Atom::Power(_) => None,
+            });
+            std::cmp::Reverse((shift, monomial.values().sum::<u32>(), *monomial))
+        });
+
+        for (idx, (monomial, coefficient)) in terms.into_iter().enumerate() {
+            match (idx, coefficient.signum() < 0) {
+                (0, true) => write!(f, "-")?,
+                (0, false) => {}
+                (_, true) => write!(f, " - ")?,
+                (_, false) => write!(f, " + ")?,
+            }
+
+            let magnitude = if coefficient.signum() < 0 {
+                -*coefficient
+            } else {
+                *coefficient
+            };
+            let atoms = monomial
+                .iter()
+                .map(|(atom, exponent)| format_atom(atom, *exponent))
+                .collect::<Vec<_>>()
+                .join("*");
+
+            match (atoms.is_empty(), magnitude == Ratio::integer(1)) {
+                (true, _) => write!(f, "{}", magnitude)?,
+                (false, true) => write!(f, "{}", atoms)?,
+                (false, false) => write!(f, "{}*{}", magnitude, atoms)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn format_atom(atom: &Atom, exponent: u32) -> String {
+    let base = match *atom {
+        Atom::Power(symbol) => symbol.to_string(),
+        Atom::Factorial(symbol, 0) => format!("{}!", symbol),
+        Atom::Factorial(symbol, shift) if shift < 0 => format!("({} - {})!", symbol, -shift),
+        Atom::Factorial(symbol, shift) => format!("({} + {})!", symbol, shift),
+    };
+
+    match exponent {
+        1 => base,
+        exponent => format!("{}^{}", base, exponent),
+    }
+}
+
+pub fn factorial(value: i128) -> Option<i128> {
+    (value >= 0).then_some(())?;
+
+    (1..=value).try_fold(1i128, |product, factor| product.checked_mul(factor))
+}
+
+/// See [`Expr::positive_from`], for the polynomial with the given coefficients (lowest power first).
+fn positive_polynomial_from(coefficients: &[Ratio]) -> Option<i128> {
+    let leading = coefficients.last()?;
+    if leading.signum() <= 0 {
+        return None;
+    }
+
+    // Cauchy's bound: every real root lies strictly within `bound` of zero
+    let bound = coefficients
+        .iter()
+        .map(|c| (c.as_f64() / leading.as_f64()).abs())
+        .fold(0.0, f64::max)
+        .ceil()
+        + 1.0;
+    if bound > MAX_SCAN {
+        return None;
+    }
+
+    let bound = bound as i128;
+    let evaluate = |value: i128| {
+        coefficients
+            .iter()
+            .rev()
+            .fold(Ratio::integer(0), |sum, c| sum * Ratio::integer(value) + *c)
+    };
+
+    Some(
+        (-bound..=bound)
+            .rev()
+            .find(|value| evaluate(*value).signum() <= 0)
+            .map_or(i128::MIN, |value| value + 1),
+    )
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    match b {
+        0 => a.abs().max(1),
+        _ => gcd(b, a % b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const N: Symbol = Symbol::Initial(Register::A);
+
+    fn n() -> Expr {
+        Expr::symbol(N)
+    }
+
+    #[test]
+    fn simplifies_factorials() {
+        let product = &(&n() * &(&n() - &Expr::constant(1))) * &Expr::factorial(N, -2);
+
+        assert_eq!(Expr::factorial(N, 0), product);
+        assert_eq!(Some(Ratio::integer(5_040)), product.evaluate(N, 7));
+    }
+
+    #[test]
+    fn formats_formulas() {
+        let square = &(&n() * &n()).scale(Ratio::integer(3)) - &n().scale(Ratio::new(1, 2));
+        let formula = &(&Expr::factorial(N, 0) + &square) + &Expr::constant(6_853);
+
+        assert_eq!("a0! + 3*a0^2 - 1/2*a0 + 6853", formula.to_string());
+        assert_eq!("(a0 - 2)!", Expr::factorial(N, -2).to_string());
+        assert_eq!("-a0", (-&n()).to_string());
+        assert_eq!("0", Expr::default().to_string());
+    }
+
+    #[test]
+    fn bounds_positive_values() {
+        let quadratic = &(&n() * &n()) - &n().scale(Ratio::integer(5));
+
+        assert_eq!(Some(6), quadratic.positive_from(N));
+        assert_eq!(Some(1), quadratic.asymptotic_sign(N));
+        assert_eq!(Some(i128::MIN), Expr::constant(3).positive_from(N));
+        assert_eq!(None, (-&n()).positive_from(N));
+        assert_eq!(Some(2), Expr::factorial(N, -2).positive_from(N));
+    }
+}
@@ -0,0 +1,223 @@
+use std::cmp::Reverse;
+
+use itertools::Itertools;
+use serde_json::json;
+
+use super::{Register, Registers};
+
+/// Record of every instruction executed by a [`super::Computer`] with tracing enabled.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Trace {
+    pub events: Vec<Event>,
+    program_len: usize,
+}
+
+/// A single executed instruction, or a whole loop idiom when the run was optimized.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Event {
+    pub ip: usize,
+    pub next_ip: usize,
+    pub instruction: String,
+    pub changes: Vec<Change>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Change {
+    pub register: Register,
+    pub from: i64,
+    pub to: i64,
+}
+
+/// A loop found through its backward jump, from `start` up to and including the jump at `end`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Loop {
+    pub start: usize,
+    pub end: usize,
+    pub iterations: usize,
+}
+
+impl Trace {
+    pub fn new(program_len: usize) -> Self {
+        Self {
+            events: vec![],
+            program_len,
+        }
+    }
+
+    pub fn record(
+        &mut self,
+        ip: usize,
+        next_ip: usize,
+        instruction: String,
+        before: &Registers,
+        after: &Registers,
+    ) {
+        let changes = before
+            .file()
+            .registers()
+            .into_iter()
+            .filter(|r| before[r] != after[r])
+            .map(|register| Change {
+                register,
+                from: before[&register],
+                to: after[&register],
+            })
+            .collect();
+
+        self.events.push(Event {
+            ip,
+            next_ip,
+            instruction,
+            changes,
+        });
+    }
+
+    /// How many times each instruction got executed, indexed by instruction.
+    pub fn hit_counts(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.program_len];
+        self.events.iter().for_each(|e| counts[e.ip] += 1);
+        counts
+    }
+
+    /// The `limit` loops that went through the most iterations, most iterations first.
+    pub fn hottest_loops(&self, limit: usize) -> Vec<Loop> {
+        self.events
+            .iter()
+            .filter(|e| e.next_ip <= e.ip)
+            .counts_by(|e| (e.next_ip, e.ip))
+            .into_iter()
+            .map(|((start, end), iterations)| Loop {
+                start,
+                end,
+                iterations,
+            })
+            .sorted_by_key(|l| (Reverse(l.iterations), l.start, l.end))
+            .take(limit)
+            .collect()
+    }
+
+    /// Every event as a JSON object, one per line.
+    pub fn to_json_lines(&self) -> String {
+        self.events
+            .iter()
+            .enumerate()
+            .map(|(step, e)| {
+                let changes = e
+                    .changes
+                    .iter()
+                    .map(|c| (c.register.to_string(), json!({"from": c.from, "to": c.to})))
+                    .collect::<serde_json::Map<_, _>>();
+
+                json!({
+                    "step": step,
+                    "ip": e.ip,
+                    "next_ip": e.next_ip,
+                    "instruction": e.instruction,
+                    "changes": changes,
+                })
+                .to_string()
+            })
+            .map(|line| line + "\n")
+            .collect()
+    }
+
+    /// Human-readable profile, listing the hit count per instruction and the hottest loops.
+    pub fn summary(&self, listing: &[String], loops: usize) -> String {
+        let hits = self
+            .hit_counts()
+            .iter()
+            .zip(listing)
+            .enumerate()
+            .map(|(idx, (hits, instruction))| format!("{:>4}  {:>12}  {}", idx, hits, instruction))
+            .join("\n");
+        let loops = self
+            .hottest_loops(loops)
+            .iter()
+            .map(|l| {
+                format!(
+                    "{:>4}..={:<4} {:>12} iterations",
+                    l.start, l.end, l.iterations
+                )
+            })
+            .join("\n");
+
+        format!(
+            "{} steps\n\nHit counts:\n{}\n\nHottest loops:\n{}\n",
+            self.events.len(),
+            hits,
+            loops
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Computer;
+    use super::*;
+
+    const PROGRAM: [&str; 6] = ["cpy 3 b", "inc a", "dec b", "jnz b -2", "cpy a c", "inc c"];
+
+    #[test]
+    fn records_every_step() {
+        let computer = Computer::new(&PROGRAM, vec![])
+            .unwrap()
+            .with_trace()
+            .run(false, 0)
+            .unwrap();
+        let trace = computer.trace().unwrap();
+
+        assert_eq!(12, trace.events.len());
+        assert_eq!(vec![1, 3, 3, 3, 1, 1], trace.hit_counts());
+        assert_eq!(
+            Event {
+                ip: 2,
+                next_ip: 3,
+                instruction: "dec b".to_string(),
+                changes: vec![Change {
+                    register: Register::B,
+                    from: 3,
+                    to: 2,
+                }],
+            },
+            trace.events[2]
+        );
+        assert_eq!(
+            vec![Loop {
+                start: 1,
+                end: 3,
+                iterations: 2,
+            }],
+            trace.hottest_loops(5)
+        );
+    }
+
+    #[test]
+    fn records_fused_idioms() {
+        let computer = Computer::new(&PROGRAM, vec![])
+            .unwrap()
+            .with_trace()
+            .run(true, 0)
+            .unwrap();
+        let trace = computer.trace().unwrap();
+
+        assert_eq!(4, trace.events.len());
+        assert_eq!("a += b", trace.events[1].instruction);
+        assert_eq!(4, trace.events[1].next_ip);
+        assert!(trace.hottest_loops(5).is_empty());
+    }
+
+    #[test]
+    fn exports_json_lines() {
+        let computer = Computer::new(&PROGRAM[..2], vec![])
+            .unwrap()
+            .with_trace()
+            .run(false, 0)
+            .unwrap();
+
+        assert_eq!(
+            "{\"changes\":{\"b\":{\"from\":0,\"to\":3}},\"instruction\":\"cpy 3 b\",\"ip\":0,\"next_ip\":1,\"step\":0}\n\
+             {\"changes\":{\"a\":{\"from\":0,\"to\":1}},\"instruction\":\"inc a\",\"ip\":1,\"next_ip\":2,\"step\":1}\n",
+            computer.trace().unwrap().to_json_lines()
+        );
+    }
+}
@@ -0,0 +1,812 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Not;
+
+use super::{Architecture, Instruction, Program, Value, Word};
+
+/// What constant propagation knows about a register at some point of the program.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Known {
+    Constant(i64),
+    Unknown,
+}
+
+type State = Vec<Known>;
+
+/// Where a `jnz` goes when its value is not zero.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Target {
+    Instruction(usize),
+    /// Outside the program, halting it.
+    Exit,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TranspileError {
+    /// The `jnz` at `ip` jumps by an offset that is not known before running the program.
+    DynamicJump { ip: usize },
+    /// The `tgl` at `ip` can't be applied before running the program.
+    DynamicToggle { ip: usize },
+    /// The `in` at `ip` reads input, which the generated function doesn't take.
+    Input { ip: usize },
+    /// The jump at `ip` crosses a loop or `if` boundary, so it has no `loop`/`if` equivalent.
+    Unstructured { ip: usize },
+}
+
+impl Display for TranspileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TranspileError::DynamicJump { ip } => {
+                write!(f, "jump offset at {} is not constant", ip)
+            }
+            TranspileError::DynamicToggle { ip } => {
+                write!(f, "toggle at {} can't be resolved statically", ip)
+            }
+            TranspileError::Input { ip } => write!(f, "instruction {} reads input", ip),
+            TranspileError::Unstructured { ip } => {
+                write!(f, "jump at {} can't be expressed with loops and ifs", ip)
+            }
+        }
+    }
+}
+
+impl Error for TranspileError {}
+
+/// Translates the program into the source code of an equivalent Rust function:
+///
+/// ```text
+/// pub fn name(registers: [i32; 4], output_len: usize) -> ([i32; 4], Vec<i32>)
+/// ```
+///
+/// It returns the final registers and the output, stopping once more than `output_len` values were
+/// output, just like [`super::Computer::run`]. There is one parameter per register of the program's
+/// [`Architecture`], holding `i64`s for [`Word::I64`]. Arithmetic wraps around like the word does,
+/// for [`Word::Checked`] the function returns a `Result` instead, failing with the instruction that
+/// overflowed like [`super::OverflowError`].
+///
+/// Jump offsets held in registers and `tgl` instructions are resolved through constant propagation,
+/// a `tgl` can only be applied when it runs at most once, before its target. Backward jumps become
+/// `loop`s (with `continue` and `break` for the other jumps to their start and end), forward jumps
+/// become `if`s, or `if`/`else` when they skip an unconditional jump.
+pub fn transpile(program: &Program, name: &str) -> Result<String, TranspileError> {
+    let architecture = program.architecture;
+    if let Some(ip) = program
+        .instructions
+        .iter()
+        .position(|i| matches!(i, Instruction::In { .. }))
+    {
+        return Err(TranspileError::Input { ip });
+    }
+    let instructions = resolve(program.instructions.clone(), architecture)?;
+
+    let names = architecture
+        .registers
+        .registers()
+        .iter()
+        .map(|r| r.to_string())
+        .collect::<Vec<_>>();
+    let result = match architecture.word {
+        Word::Checked => format!("Ok(([{}], output))", names.join(", ")),
+        Word::I32 | Word::I64 => format!("([{}], output)", names.join(", ")),
+    };
+
+    let mut transpiler = Transpiler {
+        loops: find_loops(&instructions)?,
+        instructions,
+        word: architecture.word,
+        result,
+        lines: vec![],
+        stack: vec![],
+        breaks: BTreeSet::new(),
+        continues: BTreeSet::new(),
+    };
+    let falls_through = transpiler.emit(0, transpiler.instructions.len(), 1)?;
+
+    let word = match architecture.word {
+        Word::I32 | Word::Checked => "i32",
+        Word::I64 => "i64",
+    };
+    let registers = format!("[{}; {}]", word, names.len());
+    let returns = match architecture.word {
+        Word::Checked => format!("Result<({}, Vec<{}>), usize>", registers, word),
+        Word::I32 | Word::I64 => format!("({}, Vec<{}>)", registers, word),
+    };
+    let bindings = names
+        .iter()
+        .map(|n| format!("mut {}", n))
+        .collect::<Vec<_>>();
+
+    let mut code = vec![
+        "#[allow(unused_assignments, unused_mut, unused_variables)]".to_string(),
+        format!(
+            "pub fn {}(registers: {}, output_len: usize) -> {} {{",
+            name, registers, returns
+        ),
+        format!("    let [{}] = registers;", bindings.join(", ")),
+        "    let mut output = vec![];".to_string(),
+        String::new(),
+    ];
+    code.append(&mut transpiler.lines);
+    if falls_through {
+        code.push(format!("    {}", transpiler.result));
+    }
+    code.push("}".to_string());
+
+    Ok(code.join("\n") + "\n")
+}
+
+/// Applies the toggles and replaces jump offsets held in registers by constants, as far as constant
+/// propagation allows it.
+fn resolve(
+    mut instructions: Vec<Instruction>,
+    architecture: Architecture,
+) -> Result<Vec<Instruction>, TranspileError> {
+    let nop = Instruction::Jnz {
+        value: Value::C { constant: 0 },
+        offset: Value::C { constant: 0 },
+        valid: true,
+    };
+
+    'toggles: loop {
+        let states = propagate(&instructions, architecture)?;
+        let successors = successors(&instructions, &states)?;
+
+        for (ip, state) in states.iter().enumerate() {
+            let (Instruction::Tgl { register }, Some(state)) = (&instructions[ip], state) else {
+                continue;
+            };
+            let Known::Constant(offset) = state[register.index()] else {
+                return Err(TranspileError::DynamicToggle { ip });
+            };
+
+            let target = (ip as i64).checked_add(offset);
+            if let Some(target) = target
+                .and_then(|t| usize::try_from(t).ok())
+                .filter(|t| *t < instructions.len())
+            {
+                // Running once, before the target ever runs, the target can be replaced up front
+                let repeats = reachable(&successors, &successors[ip], None)[ip];
+                let target_first = reachable(&successors, &[0], Some(ip))[target];
+                if repeats || (target_first && target != ip) {
+                    return Err(TranspileError::DynamicToggle { ip });
+                }
+                instructions[target] = instructions[target].toggle();
+            }
+
+            instructions[ip] = nop.clone();
+            continue 'toggles;
+        }
+
+        for (ip, state) in states.iter().enumerate() {
+            let (Instruction::Jnz { offset, .. }, Some(state)) = (&mut instructions[ip], state)
+            else {
+                continue;
+            };
+            if let Value::R { register } = *offset {
+                if let Known::Constant(constant) = state[register.index()] {
+                    *offset = Value::C { constant };
+                }
+            }
+        }
+
+        return Ok(instructions);
+    }
+}
+
+/// Constant propagation: the registers known before each instruction, `None` for unreachable ones.
+fn propagate(
+    instructions: &[Instruction],
+    architecture: Architecture,
+) -> Result<Vec<Option<State>>, TranspileError> {
+    let mut states: Vec<Option<State>> = vec![None; instructions.len()];
+    let mut pending = vec![];
+
+    if instructions.is_empty().not() {
+        states[0] = Some(vec![Known::Unknown; architecture.registers.len()]);
+        pending.push(0);
+    }
+
+    while let Some(ip) = pending.pop() {
+        let state = states[ip].clone().unwrap();
+        let after = transfer(&instructions[ip], state.clone(), architecture.word);
+
+        for next in next_instructions(&instructions[ip], ip, &state, instructions.len())? {
+            let merged = match &states[next] {
+                None => after.clone(),
+                Some(known) => merge(known, &after),
+            };
+            if states[next].as_ref() != Some(&merged) {
+                states[next] = Some(merged);
+                pending.push(next);
+            }
+        }
+    }
+
+    Ok(states)
+}
+
+fn transfer(instruction: &Instruction, mut state: State, word: Word) -> State {
+    match *instruction {
+        Instruction::Cpy { from, to } => state[to.index()] = known(from, &state),
+        Instruction::Inc { register } => {
+            state[register.index()] = add(state[register.index()], 1, word)
+        }
+        Instruction::Dec { register } => {
+            state[register.index()] = add(state[register.index()], -1, word)
+        }
+        _ => {}
+    }
+
+    state
+}
+
+fn merge(left: &State, right: &State) -> State {
+    let mut merged = left.clone();
+    for (known, other) in merged.iter_mut().zip(right) {
+        if known != other {
+            *known = Known::Unknown;
+        }
+    }
+    merged
+}
+
+fn known(value: Value, state: &State) -> Known {
+    match value {
+        Value::R { register } => state[register.index()],
+        Value::C { constant } => Known::Constant(constant),
+    }
+}
+
+fn add(known: Known, amount: i64, word: Word) -> Known {
+    match known {
+        Known::Constant(value) => Known::Constant(word.wrap(value.wrapping_add(amount))),
+        Known::Unknown => Known::Unknown,
+    }
+}
+
+/// The instructions that can run after the one at `ip`, given what is known before it.
+fn next_instructions(
+    instruction: &Instruction,
+    ip: usize,
+    state: &State,
+    len: usize,
+) -> Result<Vec<usize>, TranspileError> {
+    let mut next = vec![];
+
+    if let Instruction::Jnz {
+        value,
+        offset,
+        valid: true,
+    } = *instruction
+    {
+        let condition = known(value, state);
+        if condition != Known::Constant(0) {
+            let Known::Constant(offset) = known(offset, state) else {
+                return Err(TranspileError::DynamicJump { ip });
+            };
+            if let Target::Instruction(target) = target(ip, offset, len) {
+                next.push(target);
+            }
+            if condition != Known::Unknown {
+                return Ok(next);
+            }
+        }
+    }
+
+    next.extend((ip + 1 < len).then_some(ip + 1));
+    Ok(next)
+}
+
+fn successors(
+    instructions: &[Instruction],
+    states: &[Option<State>],
+) -> Result<Vec<Vec<usize>>, TranspileError> {
+    instructions
+        .iter()
+        .zip(states)
+        .enumerate()
+        .map(|(ip, (instruction, state))| match state {
+            Some(state) => next_instructions(instruction, ip, state, instructions.len()),
+            None => Ok(vec![]),
+        })
+        .collect()
+}
+
+/// The instructions reachable from `start`, without going through `avoid`.
+fn reachable(successors: &[Vec<usize>], start: &[usize], avoid: Option<usize>) -> Vec<bool> {
+    let mut seen = vec![false; successors.len()];
+    let mut pending = start.to_vec();
+
+    while let Some(ip) = pending.pop() {
+        if seen[ip] || Some(ip) == avoid {
+            continue;
+        }
+        seen[ip] = true;
+        pending.extend(&successors[ip]);
+    }
+
+    seen
+}
+
+fn target(ip: usize, offset: i64, len: usize) -> Target {
+    match usize::try_from(ip as i128 + offset as i128) {
+        Ok(target) if target < len => Target::Instruction(target),
+        _ => Target::Exit,
+    }
+}
+
+/// The condition and target of a `jnz` that can jump, once its offset is resolved.
+fn jump(instruction: &Instruction, ip: usize, len: usize) -> Option<(Value, Target)> {
+    match *instruction {
+        Instruction::Jnz {
+            value: Value::C { constant: 0 },
+            ..
+        } => None,
+        Instruction::Jnz {
+            value,
+            offset: Value::C { constant },
+            valid: true,
+        } => Some((value, target(ip, constant, len))),
+        _ => None,
+    }
+}
+
+/// Loops as `(start, end)`: from the target of backward jumps up to the last jump back there.
+/// They have to be either nested or disjoint.
+fn find_loops(instructions: &[Instruction]) -> Result<Vec<(usize, usize)>, TranspileError> {
+    let mut loops: Vec<(usize, usize)> = vec![];
+
+    for (ip, instruction) in instructions.iter().enumerate() {
+        if let Some((_, Target::Instruction(start))) = jump(instruction, ip, instructions.len()) {
+            if start <= ip {
+                loops.retain(|(s, _)| *s != start);
+                loops.push((start, ip));
+            }
+        }
+    }
+
+    for (start, end) in &loops {
+        if let Some(crossing) = loops
+            .iter()
+            .find(|(s, e)| s < start && start <= e && e < end)
+        {
+            return Err(TranspileError::Unstructured { ip: crossing.1 });
+        }
+    }
+
+    Ok(loops)
+}
+
+struct Transpiler {
+    instructions: Vec<Instruction>,
+    /// The expression returning the registers and the output.
+    word: Word,
+    result: String,
+    loops: Vec<(usize, usize)>,
+    lines: Vec<String>,
+    /// The loops being emitted, innermost last.
+    stack: Vec<(usize, usize)>,
+    /// Loop starts with a `break` or `continue`, their labels are needed.
+    breaks: BTreeSet<usize>,
+    continues: BTreeSet<usize>,
+}
+
+impl Transpiler {
+    /// Emits the instructions from `start` up to (excluding) `end`, returning whether the code
+    /// can run past its end.
+    fn emit(&mut self, start: usize, end: usize, depth: usize) -> Result<bool, TranspileError> {
+        let mut ip = start;
+        let len = self.instructions.len();
+
+        while ip < end {
+            if let Some(&(_, last)) = self
+                .loops
+                .iter()
+                .find(|l| l.0 == ip && self.stack.last() != Some(l))
+            {
+                if last >= end {
+                    return Err(TranspileError::Unstructured { ip: last });
+                }
+                if self.emit_loop(ip, last, depth)?.not() {
+                    return Ok(false);
+                }
+                ip = last + 1;
+                continue;
+            }
+
+            let instruction = self.instructions[ip].clone();
+            let Some((value, target)) = jump(&instruction, ip, len) else {
+                self.emit_statement(&instruction, ip, depth);
+                ip += 1;
+                continue;
+            };
+            let unconditional = matches!(value, Value::C { .. });
+
+            match target {
+                Target::Instruction(target) if target == ip + 1 => ip += 1,
+                // Skipping over a block
+                Target::Instruction(target) if ip < target && target < end => {
+                    if unconditional {
+                        ip = target;
+                        continue;
+                    }
+
+                    // ... or choosing between two when the skipped block ends by jumping over another
+                    let otherwise = match jump(&self.instructions[target - 1], target - 1, len) {
+                        Some((Value::C { .. }, Target::Instruction(after)))
+                            if target < after && after < end =>
+                        {
+                            Some(after)
+                        }
+                        _ => None,
+                    };
+
+                    let Some(after) = otherwise else {
+                        self.line(depth, format!("if {} == 0 {{", value));
+                        self.emit(ip + 1, target, depth + 1)?;
+                        self.line(depth, "}".to_string());
+                        ip = target;
+                        continue;
+                    };
+
+                    let falls_through = if target - 1 > ip + 1 {
+                        self.line(depth, format!("if {} == 0 {{", value));
+                        let then = self.emit(ip + 1, target - 1, depth + 1)?;
+                        self.line(depth, "} else {".to_string());
+                        let otherwise = self.emit(target, after, depth + 1)?;
+                        self.line(depth, "}".to_string());
+                        then || otherwise
+                    } else {
+                        self.line(depth, format!("if {} != 0 {{", value));
+                        self.emit(target, after, depth + 1)?;
+                        self.line(depth, "}".to_string());
+                        true
+                    };
+
+                    if falls_through.not() {
+                        return Ok(false);
+                    }
+                    ip = after;
+                }
+                target => {
+                    let statement = self.jump_statement(ip, target)?;
+                    match (unconditional, statement) {
+                        (true, None) => {}
+                        (true, Some(statement)) => {
+                            self.line(depth, statement);
+                            return Ok(false);
+                        }
+                        // The jump back at the end of a loop: leave the loop when the value is 0
+                        (false, None) => {
+                            let (start, _) = *self.stack.last().unwrap();
+                            self.breaks.insert(start);
+                            self.line(depth, format!("if {} == 0 {{", value));
+                            self.line(depth + 1, format!("break {};", label(start)));
+                            self.line(depth, "}".to_string());
+                        }
+                        (false, Some(statement)) => {
+                            self.line(depth, format!("if {} != 0 {{", value));
+                            self.line(depth + 1, statement);
+                            self.line(depth, "}".to_string());
+                        }
+                    }
+                    ip += 1;
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Emits the loop, returning whether the code after it can run.
+    fn emit_loop(
+        &mut self,
+        start: usize,
+        end: usize,
+        depth: usize,
+    ) -> Result<bool, TranspileError> {
+        let opening = self.lines.len();
+        self.line(depth, String::new());
+        self.stack.push((start, end));
+        self.emit(start, end + 1, depth + 1)?;
+        self.stack.pop();
+        self.line(depth, "}".to_string());
+
+        let labelled = self.breaks.contains(&start) || self.continues.contains(&start);
+        self.lines[opening] = match labelled {
+            true => format!("{}{}: loop {{", indent(depth), label(start)),
+            false => format!("{}loop {{", indent(depth)),
+        };
+
+        Ok(self.breaks.contains(&start))
+    }
+
+    /// The statement for a jump leaving the current block, `None` for the jump back at the end of
+    /// the innermost loop (which needs no statement when unconditional).
+    fn jump_statement(
+        &mut self,
+        ip: usize,
+        target: Target,
+    ) -> Result<Option<String>, TranspileError> {
+        let Target::Instruction(target) = target else {
+            return Ok(Some(format!("return {};", self.result)));
+        };
+
+        if let Some(&(start, end)) = self.stack.iter().rev().find(|(s, _)| *s == target) {
+            if Some(&(start, end)) == self.stack.last() && end == ip {
+                return Ok(None);
+            }
+            self.continues.insert(start);
+            return Ok(Some(format!("continue {};", label(start))));
+        }
+        if let Some(&(start, _)) = self.stack.iter().rev().find(|(_, e)| e + 1 == target) {
+            self.breaks.insert(start);
+            return Ok(Some(format!("break {};", label(start))));
+        }
+
+        Err(TranspileError::Unstructured { ip })
+    }
+
+    fn emit_statement(&mut self, instruction: &Instruction, ip: usize, depth: usize) {
+        let statement = match *instruction {
+            Instruction::Cpy { from, to } => format!("{} = {};", to, from),
+            Instruction::Inc { register } => self.arithmetic(register, "add", ip),
+            Instruction::Dec { register } => self.arithmetic(register, "sub", ip),
+            Instruction::Out { value } => {
+                self.line(depth, format!("output.push({});", value));
+                self.line(depth, "if output.len() > output_len {".to_string());
+                self.line(depth + 1, format!("return {};", self.result));
+                self.line(depth, "}".to_string());
+                return;
+            }
+            // Jumps that never jump, resolved toggles and skipped instructions
+            Instruction::Jnz { .. } | Instruction::Tgl { .. } | Instruction::InvalidStep { .. } => {
+                return
+            }
+            Instruction::In { .. } => unreachable!("programs reading input are not transpiled"),
+        };
+
+        self.line(depth, statement);
+    }
+
+    /// Adds or subtracts 1, wrapping around or failing on overflow like the word does.
+    fn arithmetic(&self, register: impl Display, operation: &str, ip: usize) -> String {
+        match self.word {
+            Word::Checked => format!(
+                "{} = {}.checked_{}(1).ok_or({}_usize)?;",
+                register, register, operation, ip
+            ),
+            Word::I32 | Word::I64 => {
+                format!("{} = {}.wrapping_{}(1);", register, register, operation)
+            }
+        }
+    }
+
+    fn line(&mut self, depth: usize, line: String) {
+        self.lines.push(format!("{}{}", indent(depth), line));
+    }
+}
+
+fn label(start: usize) -> String {
+    format!("'l{}", start)
+}
+
+fn indent(depth: usize) -> String {
+    "    ".repeat(depth)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+    use std::{env, fs};
+
+    use super::super::{parse_program, Computer, RegisterFile};
+    use super::*;
+
+    const DAY_12: &str = "\
+cpy 1 a\ncpy 1 b\ncpy 26 d\njnz c 2\njnz 1 5\ncpy 7 c\ninc d\ndec c\njnz c -2\ncpy a c\ninc a\n\
+dec b\njnz b -2\ncpy c b\ndec d\njnz d -6\ncpy 18 c\ncpy 11 d\ninc a\ndec d\njnz d -2\ndec c\n\
+jnz c -5";
+
+    const DAY_25: &str = "\
+cpy a d\ncpy 7 c\ncpy 365 b\ninc d\ndec b\njnz b -2\ndec c\njnz c -5\ncpy d a\njnz 0 0\n\
+cpy a b\ncpy 0 a\ncpy 2 c\njnz b 2\njnz 1 6\ndec b\ndec c\njnz c -4\ninc a\njnz 1 -7\ncpy 2 b\n\
+jnz c 2\njnz 1 4\ndec b\ndec c\njnz 1 -4\njnz 0 0\nout b\njnz a -19\njnz 1 -21";
+
+    const BRANCHES: &str = "\
+cpy 6 b\ndec b\njnz b 2\njnz 1 10\ninc a\njnz a 3\ninc c\njnz 1 2\ninc d\njnz c -8\ndec a\n\
+jnz 1 -10\ninc b";
+
+    const TOGGLES: &str = "cpy 2 a\ntgl a\ntgl a\ntgl a\ncpy 1 a\ndec a\ndec a";
+
+    /// Toggles `out a` into `inc a`, and `out 7` into a skipped `inc 7`.
+    const TOGGLED_OUTPUTS: &str = "cpy 2 a\ntgl a\ntgl a\nout a\nout 7\nout a";
+
+    /// Counts `a` up past the largest word and `b` down past the smallest.
+    const OVERFLOWS: &str = "inc a\ndec b\nout a\nout b";
+
+    fn transpile_source(source: &str) -> Result<String, TranspileError> {
+        transpile(&parse_program(source).unwrap(), "run")
+    }
+
+    #[test]
+    fn recovers_loops_and_ifs() {
+        assert_eq!(
+            "\
+#[allow(unused_assignments, unused_mut, unused_variables)]
+pub fn run(registers: [i32; 4], output_len: usize) -> ([i32; 4], Vec<i32>) {
+    let [mut a, mut b, mut c, mut d] = registers;
+    let mut output = vec![];
+
+    b = 6;
+    'l1: loop {
+        b = b.wrapping_sub(1);
+        if b == 0 {
+            return ([a, b, c, d], output);
+        }
+        a = a.wrapping_add(1);
+        if a == 0 {
+            c = c.wrapping_add(1);
+        } else {
+            d = d.wrapping_add(1);
+        }
+        if c != 0 {
+            continue 'l1;
+        }
+        a = a.wrapping_sub(1);
+    }
+}
+",
+            transpile_source(BRANCHES).unwrap()
+        );
+    }
+
+    #[test]
+    fn applies_toggles_running_once() {
+        assert_eq!(
+            "\
+#[allow(unused_assignments, unused_mut, unused_variables)]
+pub fn run(registers: [i32; 4], output_len: usize) -> ([i32; 4], Vec<i32>) {
+    let [mut a, mut b, mut c, mut d] = registers;
+    let mut output = vec![];
+
+    a = 2;
+    a = a.wrapping_add(1);
+    return ([a, b, c, d], output);
+}
+",
+            transpile_source(TOGGLES).unwrap()
+        );
+    }
+
+    #[test]
+    fn checks_overflows_of_checked_words() {
+        let mut program = parse_program("inc a\ndec b").unwrap();
+        program.architecture.word = Word::Checked;
+
+        assert_eq!(
+            "\
+#[allow(unused_assignments, unused_mut, unused_variables)]
+pub fn run(registers: [i32; 4], output_len: usize) -> Result<([i32; 4], Vec<i32>), usize> {
+    let [mut a, mut b, mut c, mut d] = registers;
+    let mut output = vec![];
+
+    a = a.checked_add(1).ok_or(0_usize)?;
+    b = b.checked_sub(1).ok_or(1_usize)?;
+    Ok(([a, b, c, d], output))
+}
+",
+            transpile(&program, "run").unwrap()
+        );
+    }
+
+    #[test]
+    fn reports_errors() {
+        let day_23 = "cpy a b\ndec b\ncpy b c\ntgl c\ncpy -3 c\njnz 1 c";
+        let into_loop = "jnz a 2\ninc b\ninc c\ndec b\njnz b -3";
+
+        assert_eq!(
+            Err(TranspileError::DynamicToggle { ip: 3 }),
+            transpile_source(day_23)
+        );
+        assert_eq!(
+            Err(TranspileError::DynamicJump { ip: 0 }),
+            transpile_source("jnz 1 a")
+        );
+        assert_eq!(
+            Err(TranspileError::Unstructured { ip: 4 }),
+            transpile_source(into_loop)
+        );
+        assert_eq!(
+            Err(TranspileError::Input { ip: 1 }),
+            transpile_source("inc a\nin b\nout b")
+        );
+    }
+
+    /// Compiles the transpiled programs with `rustc` and compares their results with the `Computer`,
+    /// failing when there is no `rustc` to run rather than passing without comparing anything.
+    #[test]
+    fn native_code_matches_computer() {
+        let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+        if let Err(e) = Command::new(&rustc).arg("--version").output() {
+            panic!(
+                "{} is needed to compile the transpiled programs: {}",
+                rustc, e
+            );
+        }
+
+        let (max, min) = (i32::MAX as i64, i32::MIN as i64);
+        let runs: [(&str, Word, [i64; 4], usize); 13] = [
+            (DAY_12, Word::I32, [0, 0, 0, 0], 0),
+            (DAY_12, Word::I32, [0, 0, 1, 0], 0),
+            (DAY_25, Word::I32, [5, 0, 0, 0], 9),
+            (BRANCHES, Word::I32, [0, 0, 0, 0], 0),
+            (BRANCHES, Word::I32, [-1, 0, 0, 0], 0),
+            (BRANCHES, Word::I32, [-3, 0, 0, 2], 0),
+            (TOGGLES, Word::I32, [0, 0, 0, 0], 0),
+            (TOGGLED_OUTPUTS, Word::I32, [0, 0, 0, 0], 5),
+            (OVERFLOWS, Word::I32, [max, min, 0, 0], 2),
+            (OVERFLOWS, Word::I64, [i64::MAX, i64::MIN, 0, 0], 2),
+            (OVERFLOWS, Word::Checked, [0, 0, 0, 0], 2),
+            (OVERFLOWS, Word::Checked, [max, 0, 0, 0], 2),
+            (OVERFLOWS, Word::Checked, [0, min, 0, 0], 2),
+        ];
+
+        let mut source = String::new();
+        let mut main = vec!["fn main() {".to_string()];
+        let mut expected = vec![];
+
+        for (idx, (code, word, registers, output_len)) in runs.into_iter().enumerate() {
+            let mut program = parse_program(code).unwrap();
+            program.architecture.word = word;
+            source += &transpile(&program, &format!("program_{}", idx)).unwrap();
+            main.push(format!(
+                "    println!(\"{{:?}}\", program_{}({:?}, {}));",
+                idx, registers, output_len
+            ));
+
+            let file = RegisterFile::default().registers();
+            let overrides = file.iter().copied().zip(registers).collect();
+            let computer = Computer::from_program(program, overrides).run(false, output_len);
+            expected.push(match (word, computer) {
+                (Word::Checked, Ok(c)) => {
+                    format!("{:?}", Ok::<_, ()>((c.registers.values(), c.output)))
+                }
+                (Word::Checked, Err(e)) => format!("{:?}", Err::<(), _>(e.ip)),
+                (_, computer) => {
+                    let computer = computer.unwrap();
+                    format!("{:?}", (computer.registers.values(), computer.output))
+                }
+            });
+        }
+        main.push("}".to_string());
+        source += &main.join("\n");
+
+        let directory = env::temp_dir().join(format!("assembunny_to_rust_{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let source_path = directory.join("main.rs");
+        let binary_path = directory.join("main");
+        fs::write(&source_path, source).unwrap();
+
+        let compiled = Command::new(&rustc)
+            .args(["--edition", "2021", "-O", "-D", "warnings", "-o"])
+            .arg(&binary_path)
+            .arg(&source_path)
+            .output()
+            .unwrap();
+        assert!(
+            compiled.status.success(),
+            "{}",
+            String::from_utf8_lossy(&compiled.stderr)
+        );
+
+        let output = Command::new(&binary_path).output().unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(
+            expected.join("\n") + "\n",
+            String::from_utf8(output.stdout).unwrap()
+        );
+    }
+}
@@ -1,3 +1,5 @@
-mod computer;
+pub mod answers;
+pub mod computer;
+pub mod puzzle;
 pub mod solutions;
-mod util;
+pub mod util;
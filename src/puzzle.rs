@@ -0,0 +1,507 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::Read;
+use std::ops::Not;
+use std::path::PathBuf;
+use std::{env, io};
+
+use chrono::{DateTime, Datelike, FixedOffset, Utc};
+use serde_json::Value as Json;
+
+pub use history::{Attempt, Feedback, History, Refusal};
+pub use problem::Problem;
+
+mod history;
+mod problem;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+/// Puzzles unlock at midnight in this time zone, UTC-5.
+const PUZZLE_OFFSET: i32 = -5 * 3600;
+
+/// The config file, holding JSON like `{"session": "53616c74...", "base_url": "http://localhost:8080"}`.
+pub const CONFIG_PATH: &str = "./aoc.json";
+const DEFAULT_CACHE_DIR: &str = "./.cache/aoc";
+
+const USER_AGENT: &str = "github.com/bram-inniger/advent-of-code-2016";
+
+/// Talks to the puzzle server, caching every complete page on disk so it is only ever requested once.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Client {
+    base_url: String,
+    session: Option<String>,
+    cache_dir: PathBuf,
+}
+
+impl Client {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: None,
+            cache_dir: PathBuf::from(DEFAULT_CACHE_DIR),
+        }
+    }
+
+    /// A client configured by [`CONFIG_PATH`], if it exists, where the environment variables
+    /// `AOC_SESSION`, `AOC_BASE_URL` and `AOC_CACHE_DIR` take precedence.
+    pub fn from_config() -> Result<Self, PuzzleError> {
+        let config = match fs::read_to_string(CONFIG_PATH) {
+            Ok(config) => serde_json::from_str(&config)
+                .map_err(|e| PuzzleError::Config(format!("{}: {}", CONFIG_PATH, e)))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Json::Null,
+            Err(e) => return Err(PuzzleError::Io(e.to_string())),
+        };
+        let setting = |variable: &str, key: &str| {
+            env::var(variable)
+                .ok()
+                .or_else(|| config[key].as_str().map(str::to_string))
+                .filter(|value| value.trim().is_empty().not())
+        };
+
+        let mut client = Self::new(
+            &setting("AOC_BASE_URL", "base_url").unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+        );
+        client.session = setting("AOC_SESSION", "session");
+        if let Some(cache_dir) = setting("AOC_CACHE_DIR", "cache_dir") {
+            client.cache_dir = PathBuf::from(cache_dir);
+        }
+
+        Ok(client)
+    }
+
+    pub fn with_session(mut self, session: &str) -> Self {
+        self.session = Some(session.trim().to_string());
+        self
+    }
+
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = cache_dir.into();
+        self
+    }
+
+    pub fn has_session(&self) -> bool {
+        self.session.is_some()
+    }
+
+    /// The personal puzzle input of the day.
+    pub fn input(&self, year: u16, day: u8) -> Result<String, PuzzleError> {
+        self.cached(
+            year,
+            &format!("day_{:0>2}/input.txt", day),
+            |_| true,
+            || self.get(&format!("/{}/day/{}/input", year, day)),
+        )
+    }
+
+    /// The statement of the puzzle, with part 2 once it is unlocked.
+    ///
+    /// The page is public, but only shows part 2 to a session that solved part 1. It is cached once
+    /// both parts are on it.
+    pub fn problem(&self, year: u16, day: u8) -> Result<Problem, PuzzleError> {
+        let path = format!("/{}/day/{}", year, day);
+        let page = self.cached(
+            year,
+            &format!("day_{:0>2}/problem.html", day),
+            |page| Problem::parse(page, "").parts == 2,
+            || self.fetch(&path, self.session.as_deref()),
+        )?;
+
+        Ok(Problem::parse(&page, &format!("{}{}", self.base_url, path)))
+    }
+
+    /// Submits the answer to the part, unless the history of earlier attempts tells it can't be right.
+    ///
+    /// The attempt is added to the history, which is kept as JSON in the cache directory.
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<Feedback, PuzzleError> {
+        let path = self.history_path(year);
+        let mut history = History::load(&path)?;
+        history
+            .check(day, part, answer)
+            .map_err(PuzzleError::Refused)?;
+
+        let session = self.session.as_ref().ok_or(PuzzleError::MissingSession)?;
+        let path = format!("/{}/day/{}/answer", year, day);
+        let page = self.send(
+            ureq::post(&format!("{}{}", self.base_url, path))
+                .set("Cookie", &format!("session={}", session))
+                .set("User-Agent", USER_AGENT)
+                .send_form(&[("level", &part.to_string()), ("answer", answer)]),
+            &path,
+        )?;
+        let feedback = Feedback::parse(&page).ok_or(PuzzleError::Response(path))?;
+
+        history.attempts.push(Attempt {
+            day,
+            part,
+            answer: answer.to_string(),
+            feedback,
+            time: Utc::now().to_rfc3339(),
+        });
+        history.save(&self.history_path(year))?;
+
+        Ok(feedback)
+    }
+
+    pub fn history_path(&self, year: u16) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join("submissions.json")
+    }
+
+    /// The cached file, or else the response of the request, stored in the cache when it succeeds
+    /// and is complete, as it won't change anymore.
+    fn cached(
+        &self,
+        year: u16,
+        name: &str,
+        complete: impl Fn(&str) -> bool,
+        request: impl FnOnce() -> Result<String, PuzzleError>,
+    ) -> Result<String, PuzzleError> {
+        let path = self.cache_dir.join(year.to_string()).join(name);
+        if let Ok(cached) = fs::read_to_string(&path) {
+            return Ok(cached);
+        }
+
+        let response = request()?;
+        if complete(&response) {
+            let io = |e: io::Error| PuzzleError::Io(format!("{}: {}", path.display(), e));
+            fs::create_dir_all(path.parent().unwrap()).map_err(io)?;
+            fs::write(&path, &response).map_err(io)?;
+        }
+
+        Ok(response)
+    }
+
+    fn get(&self, path: &str) -> Result<String, PuzzleError> {
+        let session = self.session.as_ref().ok_or(PuzzleError::MissingSession)?;
+        self.fetch(path, Some(session))
+    }
+
+    /// Requests the page, with the session when there is one.
+    fn fetch(&self, path: &str, session: Option<&str>) -> Result<String, PuzzleError> {
+        let mut request =
+            ureq::get(&format!("{}{}", self.base_url, path)).set("User-Agent", USER_AGENT);
+        if let Some(session) = session {
+            request = request.set("Cookie", &format!("session={}", session));
+        }
+
+        self.send(request.call(), path)
+    }
+
+    /// The body of the response, or the error its status stands for.
+    fn send(
+        &self,
+        response: Result<ureq::Response, ureq::Error>,
+        path: &str,
+    ) -> Result<String, PuzzleError> {
+        match response {
+            Ok(response) => {
+                let mut body = String::new();
+                response
+                    .into_reader()
+                    .read_to_string(&mut body)
+                    .map_err(|e| PuzzleError::Transport(e.to_string()))?;
+                Ok(body)
+            }
+            Err(ureq::Error::Status(status, response)) => Err(match status {
+                // The server answers 400 when the session is missing or expired
+                400 | 401 | 403 => PuzzleError::Auth,
+                404 => PuzzleError::NotFound(path.to_string()),
+                429 => PuzzleError::RateLimited {
+                    retry_after: response
+                        .header("Retry-After")
+                        .and_then(|s| s.trim().parse().ok()),
+                },
+                _ => PuzzleError::Status(status),
+            }),
+            Err(ureq::Error::Transport(e)) => Err(PuzzleError::Transport(e.to_string())),
+        }
+    }
+}
+
+/// The year of the most recent event at that time, with the day when a puzzle unlocked on that date.
+pub fn puzzle_date(now: DateTime<Utc>) -> (u16, Option<u8>) {
+    let now = now.with_timezone(&FixedOffset::east_opt(PUZZLE_OFFSET).unwrap());
+    let year = now.year() as u16;
+
+    match (now.month(), now.day()) {
+        (12, day) if day <= 25 => (year, Some(day as u8)),
+        (12, _) => (year, None),
+        _ => (year - 1, None),
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PuzzleError {
+    /// No session token was configured.
+    MissingSession,
+    /// The server rejected the session token.
+    Auth,
+    /// The page doesn't exist, usually because the puzzle isn't unlocked yet.
+    NotFound(String),
+    /// Too many requests, with the seconds to wait when the server told.
+    RateLimited { retry_after: Option<u64> },
+    /// Any other unexpected HTTP status.
+    Status(u16),
+    /// The server could not be reached.
+    Transport(String),
+    /// The cache could not be read or written.
+    Io(String),
+    /// The config file is invalid.
+    Config(String),
+    /// The answer was not submitted, as the history tells it can't be right.
+    Refused(Refusal),
+    /// The response to the request to the path was not understood.
+    Response(String),
+}
+
+impl Display for PuzzleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PuzzleError::MissingSession => write!(
+                f,
+                "no session token, set `AOC_SESSION` or `session` in {}",
+                CONFIG_PATH
+            ),
+            PuzzleError::Auth => write!(f, "the session token was rejected, it may have expired"),
+            PuzzleError::NotFound(path) => write!(f, "{} not found, is the puzzle unlocked?", path),
+            PuzzleError::RateLimited {
+                retry_after: Some(seconds),
+            } => write!(f, "rate limited, retry after {} seconds", seconds),
+            PuzzleError::RateLimited { retry_after: None } => {
+                write!(f, "rate limited, retry later")
+            }
+            PuzzleError::Status(status) => write!(f, "unexpected HTTP status {}", status),
+            PuzzleError::Transport(error) => write!(f, "could not reach the server: {}", error),
+            PuzzleError::Io(error) => write!(f, "cache error: {}", error),
+            PuzzleError::Config(error) => write!(f, "invalid config: {}", error),
+            PuzzleError::Refused(refusal) => write!(f, "not submitted, {}", refusal),
+            PuzzleError::Response(path) => write!(f, "unexpected response from {}", path),
+        }
+    }
+}
+
+impl Error for PuzzleError {}
+
+/// A puzzle server on localhost answering every request in turn with the next canned response,
+/// recording the requests it received.
+#[cfg(test)]
+pub(crate) mod mock {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use std::{env, fs, process, thread};
+
+    pub(crate) type Requests = Arc<Mutex<Vec<String>>>;
+
+    /// An empty directory of the test, unique to this test run so concurrent runs don't share it.
+    pub(crate) fn temp_dir(test: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-{}-{}", test, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// Starts the server, returning its base URL.
+    pub(crate) fn serve(responses: Vec<(u16, &'static str, &'static str)>) -> (String, Requests) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Requests::default();
+
+        let received = requests.clone();
+        thread::spawn(move || {
+            for ((status, headers, body), stream) in responses.into_iter().zip(listener.incoming())
+            {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request = String::new();
+                let mut len = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        len = value.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                    request.push_str(&line);
+                }
+                let mut body_in = vec![0; len];
+                reader.read_exact(&mut body_in).unwrap();
+                request.push_str(&String::from_utf8(body_in).unwrap());
+                received.lock().unwrap().push(request);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Mock\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    headers,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        (url, requests)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    fn client(url: &str, cache_dir: &Path) -> Client {
+        Client::new(url)
+            .with_session("s3cr3t")
+            .with_cache_dir(cache_dir)
+    }
+
+    #[test]
+    fn fetches_inputs_once() {
+        let (url, requests) = mock::serve(vec![(200, "", "R2, L3\n")]);
+        let client = client(&url, &mock::temp_dir("fetches-inputs-once"));
+
+        assert_eq!(Ok("R2, L3\n".to_string()), client.input(2016, 1));
+        assert_eq!(Ok("R2, L3\n".to_string()), client.input(2016, 1));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(1, requests.len());
+        assert!(requests[0].starts_with("GET /2016/day/1/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("session=s3cr3t"));
+    }
+
+    #[test]
+    fn reports_server_errors() {
+        let (url, _) = mock::serve(vec![
+            (
+                400,
+                "",
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+            ),
+            (429, "Retry-After: 60\r\n", ""),
+            (404, "", ""),
+            (500, "", ""),
+        ]);
+        let cache_dir = mock::temp_dir("reports-server-errors");
+        let client = client(&url, &cache_dir);
+
+        assert_eq!(Err(PuzzleError::Auth), client.input(2016, 1));
+        assert_eq!(
+            Err(PuzzleError::RateLimited {
+                retry_after: Some(60)
+            }),
+            client.input(2016, 1)
+        );
+        assert_eq!(
+            Err(PuzzleError::NotFound("/2016/day/1/input".to_string())),
+            client.input(2016, 1)
+        );
+        assert_eq!(Err(PuzzleError::Status(500)), client.input(2016, 1));
+        assert!(cache_dir.exists().not());
+
+        let client = Client::new(&url).with_cache_dir(cache_dir);
+        assert_eq!(Err(PuzzleError::MissingSession), client.input(2016, 1));
+    }
+
+    #[test]
+    fn fetches_problems_until_both_parts_are_unlocked() {
+        let part_1 =
+            "<article><h2>--- Day 1: Taxicab ---</h2><pre><code>R2, L3</code></pre></article>";
+        let both =
+            "<article><h2>--- Day 1: Taxicab ---</h2><pre><code>R2, L3</code></pre></article>\
+                    <article><h2>--- Part Two ---</h2></article>";
+        let (url, requests) = mock::serve(vec![(200, "", part_1), (200, "", both)]);
+        let cache_dir = mock::temp_dir("fetches-problems");
+
+        let client = Client::new(&url).with_cache_dir(&cache_dir);
+        let problem = client.problem(2016, 1).unwrap();
+        assert_eq!(1, problem.parts);
+        assert_eq!(Some("R2, L3".to_string()), problem.sample);
+
+        let client = client.with_session("s3cr3t");
+        for _ in 0..2 {
+            let problem = client.problem(2016, 1).unwrap();
+            assert_eq!(2, problem.parts);
+            assert_eq!(
+                "## Day 1: Taxicab\n\n```\nR2, L3\n```\n\n## Part Two\n",
+                problem.markdown
+            );
+        }
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(2, requests.len());
+        assert!(requests[0].starts_with("GET /2016/day/1 HTTP/1.1\r\n"));
+        assert!(requests[0].contains("session=").not());
+        assert!(requests[1].contains("session=s3cr3t"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, requests) = mock::serve(vec![
+            (
+                200,
+                "",
+                "<p>That's not the right answer; your answer is too high.</p>",
+            ),
+            (
+                200,
+                "",
+                "<p>You gave an answer too recently.  You have 35s left to wait.</p>",
+            ),
+            (200, "", "<p>That's the right answer!</p>"),
+            (200, "", "<p>Welcome!</p>"),
+        ]);
+        let cache_dir = mock::temp_dir("submits-answers");
+        let client = client(&url, &cache_dir);
+
+        assert_eq!(Ok(Feedback::TooHigh), client.submit(2016, 12, 1, "400000"));
+        assert_eq!(
+            Err(PuzzleError::Refused(Refusal::TooHigh(400_000))),
+            client.submit(2016, 12, 1, "400001")
+        );
+        assert_eq!(Ok(Feedback::Wait(35)), client.submit(2016, 12, 1, "318009"));
+        assert_eq!(Ok(Feedback::Correct), client.submit(2016, 12, 1, "318009"));
+        assert_eq!(
+            Err(PuzzleError::Response("/2016/day/12/answer".to_string())),
+            client.submit(2016, 12, 2, "9227663")
+        );
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(4, requests.len());
+        assert!(requests[0].starts_with("POST /2016/day/12/answer HTTP/1.1\r\n"));
+        assert!(requests[0].ends_with("\r\nlevel=1&answer=400000"));
+
+        let history = History::load(&client.history_path(2016)).unwrap();
+        let feedback = history
+            .attempts
+            .iter()
+            .map(|a| a.feedback)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![Feedback::TooHigh, Feedback::Wait(35), Feedback::Correct],
+            feedback
+        );
+    }
+
+    #[test]
+    fn dates_puzzles_in_their_time_zone() {
+        let date = |date: &str| puzzle_date(date.parse().unwrap());
+
+        assert_eq!((2016, Some(1)), date("2016-12-01T05:00:00Z"));
+        assert_eq!((2015, None), date("2016-12-01T04:59:59Z"));
+        assert_eq!((2016, Some(25)), date("2016-12-26T04:59:59Z"));
+        assert_eq!((2016, None), date("2016-12-26T05:00:00Z"));
+        assert_eq!((2016, None), date("2017-07-14T12:00:00Z"));
+    }
+}
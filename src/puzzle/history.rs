@@ -0,0 +1,347 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde_json::{json, Value as Json};
+
+use super::PuzzleError;
+
+/// What the server said about a submitted answer.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Feedback {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, with the seconds left to wait.
+    Wait(u64),
+    /// The part was solved before.
+    AlreadySolved,
+}
+
+impl Feedback {
+    /// Reads the feedback from the HTML page the server answers with.
+    pub fn parse(page: &str) -> Option<Self> {
+        if page.contains("That's the right answer") {
+            return Some(Feedback::Correct);
+        }
+        if page.contains("That's not the right answer") {
+            return Some(match page {
+                _ if page.contains("your answer is too high") => Feedback::TooHigh,
+                _ if page.contains("your answer is too low") => Feedback::TooLow,
+                _ => Feedback::Wrong,
+            });
+        }
+        if page.contains("You gave an answer too recently") {
+            // "You have 1m 5s left to wait.", or just "You have 35s left to wait."
+            let wait = page
+                .split("You have ")
+                .nth(1)?
+                .split(" left to wait")
+                .next()?;
+            let seconds = wait.split(' ').try_fold(0, |seconds, part| {
+                match part.split_at(part.len().checked_sub(1)?) {
+                    (minutes, "m") => Some(seconds + 60 * minutes.parse::<u64>().ok()?),
+                    (secs, "s") => Some(seconds + secs.parse::<u64>().ok()?),
+                    _ => None,
+                }
+            })?;
+            return Some(Feedback::Wait(seconds));
+        }
+        if page.contains("You don't seem to be solving the right level") {
+            return Some(Feedback::AlreadySolved);
+        }
+
+        None
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Feedback::Correct => "correct",
+            Feedback::Wrong => "wrong",
+            Feedback::TooHigh => "too_high",
+            Feedback::TooLow => "too_low",
+            Feedback::Wait(_) => "wait",
+            Feedback::AlreadySolved => "already_solved",
+        }
+    }
+}
+
+impl Display for Feedback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Feedback::Correct => write!(f, "that's the right answer"),
+            Feedback::Wrong => write!(f, "that's not the right answer"),
+            Feedback::TooHigh => write!(f, "that's not the right answer, it is too high"),
+            Feedback::TooLow => write!(f, "that's not the right answer, it is too low"),
+            Feedback::Wait(seconds) => write!(f, "answered too recently, wait {} seconds", seconds),
+            Feedback::AlreadySolved => write!(f, "the part was already solved"),
+        }
+    }
+}
+
+/// A submitted answer and the feedback on it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub feedback: Feedback,
+    /// When it was submitted, as an RFC 3339 timestamp.
+    pub time: String,
+}
+
+/// Every answer submitted, stored as JSON to refuse guesses that can't be right before sending them.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct History {
+    pub attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// Reads the history from the file, empty when it doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self, PuzzleError> {
+        let json = match fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(PuzzleError::Io(format!("{}: {}", path.display(), e))),
+        };
+        let invalid = || PuzzleError::Config(format!("{}: invalid history", path.display()));
+        let json: Json = serde_json::from_str(&json).map_err(|_| invalid())?;
+
+        let attempts = json
+            .as_array()
+            .ok_or_else(invalid)?
+            .iter()
+            .map(|attempt| {
+                let feedback = match attempt["feedback"].as_str() {
+                    Some("correct") => Feedback::Correct,
+                    Some("wrong") => Feedback::Wrong,
+                    Some("too_high") => Feedback::TooHigh,
+                    Some("too_low") => Feedback::TooLow,
+                    Some("wait") => Feedback::Wait(attempt["wait"].as_u64()?),
+                    Some("already_solved") => Feedback::AlreadySolved,
+                    _ => return None,
+                };
+
+                Some(Attempt {
+                    day: u8::try_from(attempt["day"].as_u64()?).ok()?,
+                    part: u8::try_from(attempt["part"].as_u64()?).ok()?,
+                    answer: attempt["answer"].as_str()?.to_string(),
+                    feedback,
+                    time: attempt["time"].as_str()?.to_string(),
+                })
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid)?;
+
+        Ok(Self { attempts })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), PuzzleError> {
+        let attempts = self
+            .attempts
+            .iter()
+            .map(|attempt| {
+                let mut json = json!({
+                    "day": attempt.day,
+                    "part": attempt.part,
+                    "answer": attempt.answer,
+                    "feedback": attempt.feedback.name(),
+                    "time": attempt.time,
+                });
+                if let Feedback::Wait(seconds) = attempt.feedback {
+                    json["wait"] = json!(seconds);
+                }
+                json
+            })
+            .collect::<Vec<_>>();
+
+        let io = |e: io::Error| PuzzleError::Io(format!("{}: {}", path.display(), e));
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(io)?;
+        }
+        fs::write(
+            path,
+            serde_json::to_string_pretty(&attempts).unwrap() + "\n",
+        )
+        .map_err(io)
+    }
+
+    /// Why submitting the answer is pointless, when the history tells.
+    ///
+    /// Numeric answers are checked against the lowest answer known to be too high and the highest
+    /// one known to be too low.
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Result<(), Refusal> {
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|a| a.day == day && a.part == part)
+            .collect::<Vec<_>>();
+
+        if let Some(correct) = attempts.iter().find(|a| a.feedback == Feedback::Correct) {
+            return Err(Refusal::Solved(correct.answer.clone()));
+        }
+        let wrong = [Feedback::Wrong, Feedback::TooHigh, Feedback::TooLow];
+        if attempts
+            .iter()
+            .any(|a| a.answer == answer && wrong.contains(&a.feedback))
+        {
+            return Err(Refusal::KnownWrong(answer.to_string()));
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        let bound = |feedback| {
+            attempts
+                .iter()
+                .filter(move |a| a.feedback == feedback)
+                .filter_map(|a| a.answer.parse::<i128>().ok())
+        };
+        if let Some(high) = bound(Feedback::TooHigh).min().filter(|high| value >= *high) {
+            return Err(Refusal::TooHigh(high));
+        }
+        if let Some(low) = bound(Feedback::TooLow).max().filter(|low| value <= *low) {
+            return Err(Refusal::TooLow(low));
+        }
+
+        Ok(())
+    }
+}
+
+/// Why an answer is not submitted.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Refusal {
+    /// The part was solved already, with this answer.
+    Solved(String),
+    /// The answer was submitted before, and was wrong.
+    KnownWrong(String),
+    /// The answer is at least an answer known to be too high.
+    TooHigh(i128),
+    /// The answer is at most an answer known to be too low.
+    TooLow(i128),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::Solved(answer) => write!(f, "already solved, the answer is {}", answer),
+            Refusal::KnownWrong(answer) => {
+                write!(f, "{} was already submitted and is wrong", answer)
+            }
+            Refusal::TooHigh(high) => write!(f, "the answer must be lower than {}", high),
+            Refusal::TooLow(low) => write!(f, "the answer must be higher than {}", low),
+        }
+    }
+}
+
+impl Error for Refusal {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(answer: &str, feedback: Feedback) -> Attempt {
+        Attempt {
+            day: 12,
+            part: 1,
+            answer: answer.to_string(),
+            feedback,
+            time: "2016-12-12T05:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_feedback() {
+        let page = |text| format!("<main><article><p>{}</p></article></main>", text);
+
+        assert_eq!(
+            Some(Feedback::Correct),
+            Feedback::parse(&page(
+                "That's the right answer!  You are one gold star closer."
+            ))
+        );
+        assert_eq!(
+            Some(Feedback::TooLow),
+            Feedback::parse(&page(
+                "That's not the right answer; your answer is too low.  Please wait one minute."
+            ))
+        );
+        assert_eq!(
+            Some(Feedback::Wrong),
+            Feedback::parse(&page("That's not the right answer.  If you're stuck, ..."))
+        );
+        assert_eq!(
+            Some(Feedback::Wait(65)),
+            Feedback::parse(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 1m 5s left to wait."
+            ))
+        );
+        assert_eq!(
+            Some(Feedback::Wait(35)),
+            Feedback::parse(&page(
+                "You gave an answer too recently.  You have 35s left to wait."
+            ))
+        );
+        assert_eq!(
+            Some(Feedback::AlreadySolved),
+            Feedback::parse(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ))
+        );
+        assert_eq!(None, Feedback::parse(&page("Something else")));
+    }
+
+    #[test]
+    fn refuses_pointless_answers() {
+        let history = History {
+            attempts: vec![
+                attempt("300", Feedback::TooLow),
+                attempt("abc", Feedback::Wrong),
+                attempt("400000", Feedback::TooHigh),
+                attempt("500000", Feedback::TooHigh),
+                attempt("320", Feedback::Wait(30)),
+            ],
+        };
+
+        assert_eq!(Ok(()), history.check(12, 1, "318009"));
+        assert_eq!(Ok(()), history.check(12, 1, "320"));
+        assert_eq!(Ok(()), history.check(12, 2, "abc"));
+        assert_eq!(
+            Err(Refusal::KnownWrong("abc".to_string())),
+            history.check(12, 1, "abc")
+        );
+        assert_eq!(Err(Refusal::TooLow(300)), history.check(12, 1, "12"));
+        assert_eq!(
+            Err(Refusal::TooHigh(400_000)),
+            history.check(12, 1, "450000")
+        );
+
+        let mut solved = history.clone();
+        solved.attempts.push(attempt("318009", Feedback::Correct));
+        assert_eq!(
+            Err(Refusal::Solved("318009".to_string())),
+            solved.check(12, 1, "318010")
+        );
+    }
+
+    #[test]
+    fn saves_and_loads() {
+        let path = super::super::mock::temp_dir("history-saves-and-loads").join("history.json");
+        let history = History {
+            attempts: vec![
+                attempt("300", Feedback::TooLow),
+                attempt("320", Feedback::Wait(30)),
+            ],
+        };
+
+        history.save(&path).unwrap();
+        assert_eq!(Ok(history), History::load(&path));
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        assert_eq!(Ok(History::default()), History::load(&path));
+    }
+}
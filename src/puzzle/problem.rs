@@ -0,0 +1,482 @@
+use std::ops::Not;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// Elements that never have children, nor a closing tag.
+const VOID_ELEMENTS: [&str; 6] = ["br", "hr", "img", "input", "link", "meta"];
+
+/// The statement of a puzzle, read from the articles on its page.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Problem {
+    /// How many parts are unlocked, each in an article of its own.
+    pub parts: usize,
+    /// The articles converted to Markdown, one after the other.
+    pub markdown: String,
+    /// The first code block, which usually is the sample input.
+    pub sample: Option<String>,
+}
+
+impl Problem {
+    /// Reads the problem from the page, with relative links resolved against the URL of the page.
+    pub fn parse(page: &str, url: &str) -> Self {
+        let articles = articles(page)
+            .into_iter()
+            .map(parse_html)
+            .collect::<Vec<_>>();
+
+        let markdown = articles
+            .iter()
+            .map(|article| blocks(article, url, 0).join("\n\n"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let sample = articles
+            .first()
+            .and_then(|article| find(article, "pre"))
+            .map(|pre| {
+                let mut sample = plain_text(pre);
+                sample.truncate(sample.trim_end().len());
+                sample
+            });
+
+        Self {
+            parts: articles.len(),
+            markdown: markdown + "\n",
+            sample,
+        }
+    }
+}
+
+/// The inner HTML of every `<article>` on the page.
+fn articles(page: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = page;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open) = rest[start..].find('>') else {
+            break;
+        };
+        let content = &rest[start + open + 1..];
+        let end = content.find("</article>").unwrap_or(content.len());
+        articles.push(&content[..end]);
+        rest = &content[end..];
+    }
+
+    articles
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Node {
+    Text(String),
+    Element {
+        tag: String,
+        attributes: Vec<(String, String)>,
+        children: Vec<Node>,
+    },
+}
+
+/// An element being parsed: its tag, attributes and the children so far.
+type Open = (String, Vec<(String, String)>, Vec<Node>);
+
+/// Parses the HTML into a tree, leniently: stray closing tags are ignored, and open ones closed.
+fn parse_html(html: &str) -> Vec<Node> {
+    // The open elements, starting with the root holding the whole tree
+    let mut stack: Vec<Open> = vec![(String::new(), vec![], vec![])];
+    let close = |stack: &mut Vec<Open>| {
+        let (tag, attributes, children) = stack.pop().unwrap();
+        stack.last_mut().unwrap().2.push(Node::Element {
+            tag,
+            attributes,
+            children,
+        });
+    };
+    let mut rest = html;
+
+    while rest.is_empty().not() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        if let (true, Some(end)) = (rest.starts_with('<'), rest.find('>')) {
+            let tag = &rest[1..end];
+            rest = &rest[end + 1..];
+
+            if let Some(name) = tag.strip_prefix('/') {
+                let name = name.trim().to_lowercase();
+                if let Some(idx) = stack.iter().rposition(|(open, _, _)| *open == name) {
+                    while stack.len() > idx.max(1) {
+                        close(&mut stack);
+                    }
+                }
+            } else {
+                let (name, attributes) = parse_tag(tag);
+                let void = VOID_ELEMENTS.contains(&name.as_str()) || tag.ends_with('/');
+                stack.push((name, attributes, vec![]));
+                if void {
+                    close(&mut stack);
+                }
+            }
+            continue;
+        }
+
+        let first = rest.chars().next().unwrap().len_utf8();
+        let end = rest[first..]
+            .find('<')
+            .map_or(rest.len(), |idx| idx + first);
+        stack
+            .last_mut()
+            .unwrap()
+            .2
+            .push(Node::Text(decode(&rest[..end])));
+        rest = &rest[end..];
+    }
+
+    while stack.len() > 1 {
+        close(&mut stack);
+    }
+    stack.pop().unwrap().2
+}
+
+/// The name of the tag, in lowercase, and its attributes.
+fn parse_tag(tag: &str) -> (String, Vec<(String, String)>) {
+    lazy_static! {
+        static ref ATTRIBUTE: Regex =
+            Regex::new(r#"([\w-]+)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>/]+)))?"#).unwrap();
+    }
+
+    let tag = tag.trim_end_matches('/');
+    let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+    let attributes = ATTRIBUTE
+        .captures_iter(attributes)
+        .map(|c| {
+            let value = c.get(2).or(c.get(3)).or(c.get(4));
+            (
+                c[1].to_lowercase(),
+                value.map_or(String::new(), |v| decode(v.as_str())),
+            )
+        })
+        .collect();
+
+    (name.to_lowercase(), attributes)
+}
+
+/// Replaces the character references in the text by the characters they stand for.
+fn decode(text: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let reference = rest.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let c = match &rest[1..end] {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                name if name.starts_with("#x") => u32::from_str_radix(&name[2..], 16)
+                    .ok()
+                    .and_then(char::from_u32),
+                name if name.starts_with('#') => name[1..].parse().ok().and_then(char::from_u32),
+                _ => None,
+            };
+            c.map(|c| (c, end))
+        });
+        match reference {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+/// The Markdown blocks of the nodes, with lists nested `depth` levels deep.
+fn blocks(nodes: &[Node], url: &str, depth: usize) -> Vec<String> {
+    let mut rendered = vec![];
+    // Text and inline elements in between blocks form a paragraph
+    let mut paragraph = vec![];
+    let flush = |paragraph: &mut Vec<Node>, rendered: &mut Vec<String>| {
+        let text = inline(paragraph, url);
+        if text.trim().is_empty().not() {
+            rendered.push(text.trim().to_string());
+        }
+        paragraph.clear();
+    };
+
+    for node in nodes {
+        let Node::Element { tag, children, .. } = node else {
+            paragraph.push(node.clone());
+            continue;
+        };
+
+        match tag.as_str() {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                flush(&mut paragraph, &mut rendered);
+                // Titles read like "--- Day 1: No Time for a Taxicab ---"
+                let title = inline(children, url);
+                let title = title
+                    .trim()
+                    .trim_start_matches("--- ")
+                    .trim_end_matches(" ---");
+                rendered.push(format!("## {}", title));
+            }
+            "p" | "div" | "article" | "section" | "main" => {
+                flush(&mut paragraph, &mut rendered);
+                rendered.extend(blocks(children, url, depth));
+            }
+            "pre" => {
+                flush(&mut paragraph, &mut rendered);
+                rendered.push(format!("```\n{}\n```", plain_text(node).trim_end()));
+            }
+            "ul" | "ol" => {
+                flush(&mut paragraph, &mut rendered);
+                rendered.push(list(tag == "ol", children, url, depth));
+            }
+            _ => paragraph.push(node.clone()),
+        }
+    }
+
+    flush(&mut paragraph, &mut rendered);
+    rendered
+}
+
+/// A list with an item per line, and the lists inside the items indented below them.
+fn list(ordered: bool, items: &[Node], url: &str, depth: usize) -> String {
+    let indent = "   ".repeat(depth);
+
+    items
+        .iter()
+        .filter_map(|item| match item {
+            Node::Element { tag, children, .. } if tag == "li" => Some(children),
+            _ => None,
+        })
+        .enumerate()
+        .map(|(idx, children)| {
+            let marker = match ordered {
+                true => format!("{}.", idx + 1),
+                false => "-".to_string(),
+            };
+            let item = blocks(children, url, depth + 1)
+                .into_iter()
+                .map(|block| match block.starts_with(&"   ".repeat(depth + 1)) {
+                    true => block,
+                    false => block.replace('\n', &format!("\n{}   ", indent)),
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!("{}{} {}", indent, marker, item)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The nodes as Markdown text on a single line.
+fn inline(nodes: &[Node], url: &str) -> String {
+    let text = nodes
+        .iter()
+        .map(|node| match node {
+            Node::Text(text) => escape(&collapse(text)),
+            Node::Element {
+                tag,
+                attributes,
+                children,
+            } => match tag.as_str() {
+                "em" | "strong" | "b" => wrap(&inline(children, url), "**"),
+                "i" => wrap(&inline(children, url), "*"),
+                "code" => code_span(children),
+                "a" => {
+                    let text = inline(children, url);
+                    match attributes.iter().find(|(name, _)| name == "href") {
+                        Some((_, href)) => format!("[{}]({})", text, resolve(url, href)),
+                        None => text,
+                    }
+                }
+                "br" => " ".to_string(),
+                _ => inline(children, url),
+            },
+        })
+        .collect::<String>();
+
+    collapse(&text)
+}
+
+/// Inline code, emphasized as a whole when all of it is, as puzzles do to highlight answers.
+fn code_span(children: &[Node]) -> String {
+    let code = children.iter().map(plain_text).collect::<String>();
+    let fence = if code.contains('`') { "``" } else { "`" };
+    let span = format!("{}{}{}", fence, code, fence);
+
+    match children {
+        [Node::Element { tag, .. }] if tag == "em" => format!("**{}**", span),
+        _ => span,
+    }
+}
+
+/// Wraps the text in the delimiters, keeping the whitespace around it outside of them.
+fn wrap(text: &str, delimiter: &str) -> String {
+    if text.trim().is_empty() {
+        return text.to_string();
+    }
+    let start = text.len() - text.trim_start().len();
+    let end = text.trim_end().len();
+
+    format!(
+        "{}{}{}{}{}",
+        &text[..start],
+        delimiter,
+        &text[start..end],
+        delimiter,
+        &text[end..]
+    )
+}
+
+/// The text of the node, exactly as it is shown.
+fn plain_text(node: &Node) -> String {
+    match node {
+        Node::Text(text) => text.clone(),
+        Node::Element { children, .. } => children.iter().map(plain_text).collect(),
+    }
+}
+
+/// The first element with the tag, searching depth first.
+fn find<'a>(nodes: &'a [Node], tag: &str) -> Option<&'a Node> {
+    nodes.iter().find_map(|node| match node {
+        Node::Element { tag: t, .. } if t == tag => Some(node),
+        Node::Element { children, .. } => find(children, tag),
+        Node::Text(_) => None,
+    })
+}
+
+/// Collapses every run of whitespace into a single space, as browsers do.
+fn collapse(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c.is_whitespace() {
+            true if collapsed.ends_with(' ') => {}
+            true => collapsed.push(' '),
+            false => collapsed.push(c),
+        }
+    }
+    collapsed
+}
+
+/// Escapes the characters Markdown would read as formatting.
+fn escape(text: &str) -> String {
+    text.chars()
+        .flat_map(|c| match c {
+            '\\' | '*' | '_' | '`' | '[' | ']' | '<' => vec!['\\', c],
+            _ => vec![c],
+        })
+        .collect()
+}
+
+/// The link as an absolute URL, relative to the page at the URL.
+fn resolve(url: &str, href: &str) -> String {
+    if href.contains("://") || href.starts_with('#') {
+        return href.to_string();
+    }
+
+    let host_end = url
+        .find("://")
+        .and_then(|scheme| url[scheme + 3..].find('/').map(|idx| idx + scheme + 3))
+        .unwrap_or(url.len());
+    match href.strip_prefix('/') {
+        Some(path) => format!("{}/{}", &url[..host_end], path),
+        None => {
+            let dir_end = url[host_end..]
+                .rfind('/')
+                .map_or(url.len(), |idx| idx + host_end);
+            format!("{}/{}", &url[..dir_end], href)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<body>
+<main>
+<article class="day-desc"><h2>--- Day 6: Signals and Noise ---</h2><p>Something is jamming your communications with Santa. Fortunately, your signal is only <em>partially</em> jammed, and protocol in situations like this is to switch to a simple <a href="https://en.wikipedia.org/wiki/Repetition_code" target="_blank">repetition code</a> to get the message through.</p>
+<p>For example, suppose you had recorded the following messages:</p>
+<pre><code>eedadn
+drvtee
+</code></pre>
+<p>The most common character in the first column is <code>e</code>; in the second, <code>a</code>. Given the recording in your <a href="6/input">puzzle input</a>, <em>what is the error-corrected version</em> of the message being sent?</p>
+<ul>
+<li>Split on <code>&lt;</code> &amp; <code>&gt;</code>, for 2 * 3 results<!-- like so -->.</li>
+<li>See <a href="/2016/day/5">day 5</a>:
+<ul><li>nested</li></ul></li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>qzedlxso</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>In this example, the less common character in the first column is <code>a</code>; so, the original message is actually <code><em>advent</em></code>.</p>
+</article>
+</main>
+</body>
+</html>"#;
+
+    #[test]
+    fn converts_articles_to_markdown() {
+        let problem = Problem::parse(PAGE, "https://adventofcode.com/2016/day/6");
+
+        let expected = "\
+## Day 6: Signals and Noise
+
+Something is jamming your communications with Santa. Fortunately, your signal is only **partially** jammed, and protocol in situations like this is to switch to a simple [repetition code](https://en.wikipedia.org/wiki/Repetition_code) to get the message through.
+
+For example, suppose you had recorded the following messages:
+
+```
+eedadn
+drvtee
+```
+
+The most common character in the first column is `e`; in the second, `a`. Given the recording in your [puzzle input](https://adventofcode.com/2016/day/6/input), **what is the error-corrected version** of the message being sent?
+
+- Split on `<` & `>`, for 2 \\* 3 results.
+- See [day 5](https://adventofcode.com/2016/day/5):
+   - nested
+
+## Part Two
+
+In this example, the less common character in the first column is `a`; so, the original message is actually **`advent`**.
+";
+        assert_eq!(expected, problem.markdown);
+        assert_eq!(2, problem.parts);
+    }
+
+    #[test]
+    fn extracts_the_first_code_block_as_sample() {
+        let problem = Problem::parse(PAGE, "https://adventofcode.com/2016/day/6");
+        assert_eq!(Some("eedadn\ndrvtee".to_string()), problem.sample);
+
+        let locked = PAGE.split("<p>Your puzzle answer").next().unwrap();
+        let problem = Problem::parse(locked, "https://adventofcode.com/2016/day/6");
+        assert_eq!(1, problem.parts);
+        assert!(problem.markdown.contains("Part Two").not());
+
+        let problem = Problem::parse("<article><p>No sample</p></article>", "");
+        assert_eq!(None, problem.sample);
+    }
+
+    #[test]
+    fn decodes_character_references() {
+        assert_eq!(
+            "<a> & \"b\" ü ü &c; &",
+            decode("&lt;a&gt; &amp; &quot;b&quot; &#252; &#xfc; &c; &")
+        );
+    }
+}
@@ -1,25 +1,225 @@
-pub mod day_01;
-pub mod day_02;
-pub mod day_03;
-pub mod day_04;
-pub mod day_05;
-pub mod day_06;
-pub mod day_07;
-pub mod day_08;
-pub mod day_09;
-pub mod day_10;
-pub mod day_11;
-pub mod day_12;
-pub mod day_13;
-pub mod day_14;
-pub mod day_15;
-pub mod day_16;
-pub mod day_17;
-pub mod day_18;
-pub mod day_19;
-pub mod day_20;
-pub mod day_21;
-pub mod day_22;
-pub mod day_23;
-pub mod day_24;
-pub mod day_25;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::str::FromStr;
+use std::{fs, io};
+
+pub mod y2016;
+
+/// The solution of a day, taking the raw input text.
+///
+/// Anything both parts need besides the input (such as the screen size of day 8) is part of the
+/// [`Solution::Options`], which default to the values of the actual puzzle.
+pub trait Solution {
+    /// The input as both parts take it.
+    type Input<'a>;
+    type Options: Default;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError>;
+
+    fn part_1(input: &Self::Input<'_>, options: &Self::Options) -> String;
+
+    /// The answer to part 2, `None` on days without one (the last day of a year).
+    fn part_2(input: &Self::Input<'_>, options: &Self::Options) -> Option<String>;
+}
+
+/// A [`Solution`] with its default options, for running any day the same way, see [`YEARS`].
+#[derive(Debug, Copy, Clone)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    parts: [Part; 2],
+}
+
+/// Solves a part from the raw input, `None` when the day has no such part.
+type Part = fn(&str) -> Result<Option<String>, InputError>;
+
+impl Day {
+    pub const fn of<S: Solution>(year: u16, day: u8) -> Self {
+        Self {
+            year,
+            day,
+            parts: [part_1::<S>, part_2::<S>],
+        }
+    }
+
+    /// The answer to part 1 or 2 for the raw input.
+    pub fn solve(&self, part: u8, input: &str) -> Result<String, SolveError> {
+        let solve = match part {
+            1 | 2 => self.parts[part as usize - 1],
+            _ => return Err(SolveError::Part(part)),
+        };
+
+        solve(input)
+            .map_err(SolveError::Input)?
+            .ok_or(SolveError::NoSuchPart(part))
+    }
+
+    /// Where the personal input of the day is kept.
+    pub fn input_path(&self) -> String {
+        format!("inputs/{}/day_{:0>2}.txt", self.year, self.day)
+    }
+
+    /// The personal input of the day.
+    ///
+    /// Inputs used to be kept without a directory per year, as `inputs/day_NN.txt`. When the input
+    /// is still there, this fails with where to move it rather than reporting it as not found.
+    pub fn read_input(&self) -> io::Result<String> {
+        fs::read_to_string(self.input_path()).map_err(|e| {
+            let old_path = format!("inputs/day_{:0>2}.txt", self.day);
+            match e.kind() == io::ErrorKind::NotFound && Path::new(&old_path).exists() {
+                true => io::Error::other(format!(
+                    "{} moved, move it to {}",
+                    old_path,
+                    self.input_path()
+                )),
+                false => io::Error::new(e.kind(), format!("{}: {}", self.input_path(), e)),
+            }
+        })
+    }
+}
+
+fn part_1<S: Solution>(input: &str) -> Result<Option<String>, InputError> {
+    Ok(Some(S::part_1(&S::parse(input)?, &S::Options::default())))
+}
+
+fn part_2<S: Solution>(input: &str) -> Result<Option<String>, InputError> {
+    Ok(S::part_2(&S::parse(input)?, &S::Options::default()))
+}
+
+/// The lines of the input, for days taking it line by line.
+pub fn lines(input: &str) -> Result<Vec<&str>, InputError> {
+    Ok(text(input)?.lines().collect())
+}
+
+/// The input without the whitespace around it, for days taking it as a whole.
+pub fn text(input: &str) -> Result<&str, InputError> {
+    match input.trim() {
+        "" => Err(InputError::Empty),
+        text => Ok(text),
+    }
+}
+
+/// A number in the input, failing with what was `expected` otherwise.
+pub fn number<T: FromStr>(text: &str, expected: &str) -> Result<T, InputError> {
+    T::from_str(text).map_err(|_| InputError::expected(expected, text))
+}
+
+/// Why the input of a day can't be parsed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum InputError {
+    Empty,
+    /// The input is not what the day expects, with why.
+    Invalid(String),
+}
+
+impl InputError {
+    pub fn expected(expected: &str, found: &str) -> Self {
+        InputError::Invalid(format!("expected {}, found `{}`", expected, found))
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Empty => write!(f, "the input is empty"),
+            InputError::Invalid(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+/// Why a day can't be solved.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SolveError {
+    /// There is no such part, only 1 and 2.
+    Part(u8),
+    /// The day doesn't have this part.
+    NoSuchPart(u8),
+    Input(InputError),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Part(part) => write!(f, "no part {}, expected 1 or 2", part),
+            SolveError::NoSuchPart(part) => write!(f, "there is no part {} that day", part),
+            SolveError::Input(e) => write!(f, "invalid input, {}", e),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+/// The days of every year, in order.
+pub static YEARS: &[&[Day]] = &[y2016::DAYS];
+
+/// Every day of every year, in order.
+pub fn days() -> impl Iterator<Item = &'static Day> {
+    YEARS.iter().flat_map(|days| days.iter())
+}
+
+/// The day of that year, if it is solved.
+pub fn day(year: u16, day: u8) -> Option<&'static Day> {
+    days().find(|d| d.year == year && d.day == day)
+}
+
+/// The most recent year with solutions.
+pub fn latest_year() -> u16 {
+    days().map(|d| d.year).max().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_every_day_in_order() {
+        assert!(y2016::DAYS.iter().map(|d| d.day).eq(1..=25));
+        assert_eq!(2016, latest_year());
+        assert_eq!(None, day(2016, 26).map(|d| d.day));
+        assert_eq!(None, day(2014, 1).map(|d| d.day));
+    }
+
+    #[test]
+    fn solves_from_the_raw_input() {
+        let sample = "ULL\nRRDDD\nLURDL\nUUUUD\n";
+
+        assert_eq!(
+            Ok("1985".to_string()),
+            day(2016, 2).unwrap().solve(1, sample)
+        );
+        assert_eq!(Ok("3".to_string()), day(2016, 19).unwrap().solve(1, "5\n"));
+
+        let options = y2016::day_13::Options {
+            destination: (7, 4),
+        };
+        assert_eq!(
+            "11",
+            y2016::day_13::Day13::part_1(&y2016::day_13::Day13::parse("10\n").unwrap(), &options)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_parts_and_inputs() {
+        let day_19 = day(2016, 19).unwrap();
+
+        assert_eq!(Err(SolveError::Part(3)), day_19.solve(3, "5\n"));
+        assert_eq!(Err(SolveError::Part(0)), day_19.solve(0, "5\n"));
+        assert_eq!(
+            Err(SolveError::Input(InputError::Empty)),
+            day(2016, 2).unwrap().solve(1, " \n")
+        );
+        assert_eq!(
+            "invalid input, expected the number of elves, found `five`",
+            day_19.solve(2, "five\n").unwrap_err().to_string()
+        );
+        assert!(day(2016, 12)
+            .unwrap()
+            .solve(1, "cpy 1 a\njump 2\n")
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid input, line 2"));
+    }
+}
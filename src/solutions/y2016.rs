@@ -0,0 +1,58 @@
+pub mod day_01;
+pub mod day_02;
+pub mod day_03;
+pub mod day_04;
+pub mod day_05;
+pub mod day_06;
+pub mod day_07;
+pub mod day_08;
+pub mod day_09;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_19;
+pub mod day_20;
+pub mod day_21;
+pub mod day_22;
+pub mod day_23;
+pub mod day_24;
+pub mod day_25;
+
+use super::Day;
+
+pub const YEAR: u16 = 2016;
+
+/// Every day of the year, in order.
+pub static DAYS: &[Day] = &[
+    Day::of::<day_01::Day01>(YEAR, 1),
+    Day::of::<day_02::Day02>(YEAR, 2),
+    Day::of::<day_03::Day03>(YEAR, 3),
+    Day::of::<day_04::Day04>(YEAR, 4),
+    Day::of::<day_05::Day05>(YEAR, 5),
+    Day::of::<day_06::Day06>(YEAR, 6),
+    Day::of::<day_07::Day07>(YEAR, 7),
+    Day::of::<day_08::Day08>(YEAR, 8),
+    Day::of::<day_09::Day09>(YEAR, 9),
+    Day::of::<day_10::Day10>(YEAR, 10),
+    Day::of::<day_11::Day11>(YEAR, 11),
+    Day::of::<day_12::Day12>(YEAR, 12),
+    Day::of::<day_13::Day13>(YEAR, 13),
+    Day::of::<day_14::Day14>(YEAR, 14),
+    Day::of::<day_15::Day15>(YEAR, 15),
+    Day::of::<day_16::Day16>(YEAR, 16),
+    Day::of::<day_17::Day17>(YEAR, 17),
+    Day::of::<day_18::Day18>(YEAR, 18),
+    Day::of::<day_19::Day19>(YEAR, 19),
+    Day::of::<day_20::Day20>(YEAR, 20),
+    Day::of::<day_21::Day21>(YEAR, 21),
+    Day::of::<day_22::Day22>(YEAR, 22),
+    Day::of::<day_23::Day23>(YEAR, 23),
+    Day::of::<day_24::Day24>(YEAR, 24),
+    Day::of::<day_25::Day25>(YEAR, 25),
+];
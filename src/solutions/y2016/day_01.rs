@@ -1,32 +1,26 @@
-use std::str::FromStr;
-
-use itertools::Itertools;
 use rustc_hash::FxHashSet;
 
-pub fn solve_1(instructions: &str) -> i32 {
+use crate::solutions::{self, InputError, Solution};
+
+pub fn solve_1(instructions: &[Instruction]) -> i32 {
     let mut direction = Direction::N;
     let mut coordinate = Coordinate { x: 0, y: 0 };
 
-    instructions
-        .split(", ")
-        .map(Instruction::new)
-        .for_each(|i| {
-            direction = direction.turn(&i.turn);
-            coordinate = coordinate.go(&direction, i.distance)
-        });
+    instructions.iter().for_each(|i| {
+        direction = direction.turn(&i.turn);
+        coordinate = coordinate.go(&direction, i.distance)
+    });
 
     coordinate.distance()
 }
 
-pub fn solve_2(instructions: &str) -> i32 {
-    let instructions = instructions.split(", ").map(Instruction::new).collect_vec();
-
+pub fn solve_2(instructions: &[Instruction]) -> i32 {
     let mut direction = Direction::N;
     let mut coordinate = Coordinate { x: 0, y: 0 };
     let mut visited: FxHashSet<Coordinate> = FxHashSet::default();
     visited.insert(coordinate);
 
-    for i in &instructions {
+    for i in instructions {
         direction = direction.turn(&i.turn);
 
         for _ in 0..i.distance {
@@ -44,22 +38,27 @@ pub fn solve_2(instructions: &str) -> i32 {
 }
 
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
-struct Instruction {
+pub struct Instruction {
     turn: Turn,
     distance: i32,
 }
 
 impl Instruction {
-    fn new(instruction: &str) -> Self {
-        let turn = match &instruction[0..1] {
-            "R" => Turn::R,
-            "L" => Turn::L,
-            _ => unreachable!(),
+    fn new(instruction: &str) -> Result<Self, InputError> {
+        let turn = match instruction.get(0..1) {
+            Some("R") => Turn::R,
+            Some("L") => Turn::L,
+            _ => {
+                return Err(InputError::expected(
+                    "an instruction like `R2`",
+                    instruction,
+                ))
+            }
         };
 
-        let distance = i32::from_str(&instruction[1..]).unwrap();
+        let distance = solutions::number(&instruction[1..], "a distance")?;
 
-        Self { turn, distance }
+        Ok(Self { turn, distance })
     }
 }
 
@@ -133,33 +132,71 @@ impl Direction {
     }
 }
 
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input<'a> = Vec<Instruction>;
+    type Options = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        solutions::text(input)?
+            .split(", ")
+            .map(Instruction::new)
+            .collect()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(input).to_string()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        Some(solve_2(input).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn parse(input: &str) -> Vec<Instruction> {
+        Day01::parse(input).unwrap()
+    }
+
     #[test]
     fn day_01_part_01_sample() {
-        assert_eq!(5, solve_1("R2, L3"));
-        assert_eq!(2, solve_1("R2, R2, R2"));
-        assert_eq!(12, solve_1("R5, L5, R5, R3"));
+        assert_eq!(5, solve_1(&parse("R2, L3")));
+        assert_eq!(2, solve_1(&parse("R2, R2, R2")));
+        assert_eq!(12, solve_1(&parse("R5, L5, R5, R3")));
     }
 
     #[test]
     fn day_01_part_01_solution() {
-        let input = include_str!("../../inputs/day_01.txt").trim();
+        let input = parse(include_str!("../../../inputs/2016/day_01.txt"));
 
-        assert_eq!(250, solve_1(input));
+        assert_eq!(250, solve_1(&input));
     }
 
     #[test]
     fn day_01_part_02_sample() {
-        assert_eq!(4, solve_2("R8, R4, R4, R8"));
+        assert_eq!(4, solve_2(&parse("R8, R4, R4, R8")));
     }
 
     #[test]
     fn day_01_part_02_solution() {
-        let input = include_str!("../../inputs/day_01.txt").trim();
+        let input = parse(include_str!("../../../inputs/2016/day_01.txt"));
+
+        assert_eq!(151, solve_2(&input));
+    }
 
-        assert_eq!(151, solve_2(input));
+    #[test]
+    fn day_01_rejects_invalid_instructions() {
+        assert_eq!(
+            Err(InputError::expected("an instruction like `R2`", "U2")),
+            Day01::parse("R2, U2")
+        );
+        assert_eq!(
+            Err(InputError::expected("a distance", "x")),
+            Day01::parse("R2, Lx")
+        );
     }
 }
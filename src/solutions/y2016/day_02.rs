@@ -1,15 +1,10 @@
-use itertools::Itertools;
-
-pub fn solve_1(instructions: &[&str]) -> String {
-    let instructions = instructions
-        .iter()
-        .map(|s| s.chars().map(Direction::new).collect_vec())
-        .collect_vec();
+use crate::solutions::{self, InputError, Solution};
 
+pub fn solve_1(instructions: &[Vec<Direction>]) -> String {
     let mut code = vec![];
     let mut keypad = SimpleKeypadKey::new();
 
-    for key in &instructions {
+    for key in instructions {
         for direction in key {
             keypad = keypad.next(direction)
         }
@@ -20,16 +15,11 @@ pub fn solve_1(instructions: &[&str]) -> String {
     code.iter().collect()
 }
 
-pub fn solve_2(instructions: &[&str]) -> String {
-    let instructions = instructions
-        .iter()
-        .map(|s| s.chars().map(Direction::new).collect_vec())
-        .collect_vec();
-
+pub fn solve_2(instructions: &[Vec<Direction>]) -> String {
     let mut code = vec![];
     let mut keypad = TrickyKeypadKey::new();
 
-    for key in &instructions {
+    for key in instructions {
         for direction in key {
             keypad = keypad.next(direction)
         }
@@ -41,7 +31,7 @@ pub fn solve_2(instructions: &[&str]) -> String {
 }
 
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
-enum Direction {
+pub enum Direction {
     U,
     R,
     D,
@@ -49,13 +39,16 @@ enum Direction {
 }
 
 impl Direction {
-    fn new(direction: char) -> Self {
+    fn new(direction: char) -> Result<Self, InputError> {
         match direction {
-            'U' => Direction::U,
-            'R' => Direction::R,
-            'D' => Direction::D,
-            'L' => Direction::L,
-            _ => unreachable!(),
+            'U' => Ok(Direction::U),
+            'R' => Ok(Direction::R),
+            'D' => Ok(Direction::D),
+            'L' => Ok(Direction::L),
+            _ => Err(InputError::expected(
+                "`U`, `R`, `D` or `L`",
+                &direction.to_string(),
+            )),
         }
     }
 }
@@ -250,40 +243,56 @@ impl TrickyKeypadKey {
     }
 }
 
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input<'a> = Vec<Vec<Direction>>;
+    type Options = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        solutions::lines(input)?
+            .into_iter()
+            .map(|line| line.chars().map(Direction::new).collect())
+            .collect()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        Some(solve_2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
 
     #[test]
     fn day_02_part_01_sample() {
-        let sample = vec!["ULL", "RRDDD", "LURDL", "UUUUD"];
+        let sample = Day02::parse("ULL\nRRDDD\nLURDL\nUUUUD").unwrap();
 
         assert_eq!("1985", solve_1(&sample));
     }
 
     #[test]
     fn day_02_part_01_solution() {
-        let input = include_str!("../../inputs/day_02.txt")
-            .lines()
-            .collect_vec();
+        let input = Day02::parse(include_str!("../../../inputs/2016/day_02.txt")).unwrap();
 
         assert_eq!("69642", solve_1(&input));
     }
 
     #[test]
     fn day_02_part_02_sample() {
-        let sample = vec!["ULL", "RRDDD", "LURDL", "UUUUD"];
+        let sample = Day02::parse("ULL\nRRDDD\nLURDL\nUUUUD").unwrap();
 
         assert_eq!("5DB3", solve_2(&sample));
     }
 
     #[test]
     fn day_02_part_02_solution() {
-        let input = include_str!("../../inputs/day_02.txt")
-            .lines()
-            .collect_vec();
+        let input = Day02::parse(include_str!("../../../inputs/2016/day_02.txt")).unwrap();
 
         assert_eq!("8CB23", solve_2(&input));
     }
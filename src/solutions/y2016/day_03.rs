@@ -0,0 +1,105 @@
+use itertools::Itertools;
+
+use crate::solutions::{self, InputError, Solution};
+
+pub fn solve_1(sides: &[[usize; 3]]) -> usize {
+    sides
+        .iter()
+        .map(|&[a, b, c]| Triangle::new(a, b, c))
+        .filter(|t| t.possible())
+        .count()
+}
+
+pub fn solve_2(sides: &[[usize; 3]]) -> usize {
+    sides
+        .chunks(3)
+        .flat_map(|ys| (0..3).map(|x| Triangle::new(ys[0][x], ys[1][x], ys[2][x])))
+        .filter(|t| t.possible())
+        .count()
+}
+
+/// The sides of a row, failing when it isn't three lengths.
+fn parse_sides(row: &str) -> Result<[usize; 3], InputError> {
+    row.split_whitespace()
+        .map(|side| solutions::number(side, "a length"))
+        .collect::<Result<Vec<_>, _>>()?
+        .try_into()
+        .map_err(|_| InputError::expected("three lengths", row))
+}
+
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+struct Triangle {
+    sides: Vec<usize>,
+}
+
+impl Triangle {
+    fn new(a: usize, b: usize, c: usize) -> Self {
+        Self {
+            sides: [a, b, c].into_iter().sorted().collect_vec(),
+        }
+    }
+
+    fn possible(&self) -> bool {
+        self.sides[0] + self.sides[1] > self.sides[2]
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input<'a> = Vec<[usize; 3]>;
+    type Options = ();
+
+    /// The rows of sides, which come in groups of three rows for part 2.
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        let sides = solutions::lines(input)?
+            .into_iter()
+            .map(parse_sides)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match sides.len() % 3 {
+            0 => Ok(sides),
+            _ => Err(InputError::Invalid(format!(
+                "expected the rows in groups of three, found {} rows",
+                sides.len()
+            ))),
+        }
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(input).to_string()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        Some(solve_2(input).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_03_part_01_sample() {
+        // No sample inputs for part 1
+    }
+
+    #[test]
+    fn day_03_part_01_solution() {
+        let input = Day03::parse(include_str!("../../../inputs/2016/day_03.txt")).unwrap();
+
+        assert_eq!(862, solve_1(&input));
+    }
+
+    #[test]
+    fn day_03_part_02_sample() {
+        // No sample inputs for part 2
+    }
+
+    #[test]
+    fn day_03_part_02_solution() {
+        let input = Day03::parse(include_str!("../../../inputs/2016/day_03.txt")).unwrap();
+
+        assert_eq!(1_577, solve_2(&input));
+    }
+}
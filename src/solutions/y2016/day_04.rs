@@ -1,23 +1,18 @@
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use std::cmp::Ordering;
-use std::str::FromStr;
 
 use regex::Regex;
 
-pub fn solve_1(rooms: &[&str]) -> u32 {
-    rooms
-        .iter()
-        .map(|s| Room::new(s))
-        .filter(|r| r.real())
-        .map(|r| r.sector)
-        .sum()
+use crate::solutions::{self, InputError, Solution};
+
+pub fn solve_1(rooms: &[Room]) -> u32 {
+    rooms.iter().filter(|r| r.real()).map(|r| r.sector).sum()
 }
 
-pub fn solve_2(rooms: &[&str]) -> u32 {
+pub fn solve_2(rooms: &[Room]) -> u32 {
     rooms
         .iter()
-        .map(|s| Room::new(s))
         .filter(|r| r.real())
         .find(|r| r.decrypt() == "northpole object storage")
         .map(|r| r.sector)
@@ -30,25 +25,27 @@ lazy_static! {
 }
 
 #[derive(Debug)]
-struct Room<'a> {
+pub struct Room<'a> {
     name: &'a str,
     sector: u32,
     checksum: Vec<char>,
 }
 
 impl<'a> Room<'a> {
-    fn new(room: &'a str) -> Self {
-        let caps = RE.captures(room).unwrap();
+    fn new(room: &'a str) -> Result<Self, InputError> {
+        let caps = RE
+            .captures(room)
+            .ok_or_else(|| InputError::expected("a room like `a-b-c-123[abcde]`", room))?;
 
         let name = caps.name("name").unwrap().as_str();
-        let sector = u32::from_str(caps.name("sector").unwrap().as_str()).unwrap();
+        let sector = solutions::number(caps.name("sector").unwrap().as_str(), "a sector ID")?;
         let checksum = caps.name("check").unwrap().as_str().chars().collect();
 
-        Self {
+        Ok(Self {
             name,
             sector,
             checksum,
-        }
+        })
     }
 
     fn real(&self) -> bool {
@@ -97,29 +94,48 @@ impl<'a> Room<'a> {
     }
 }
 
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input<'a> = Vec<Room<'a>>;
+    type Options = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        solutions::lines(input)?
+            .into_iter()
+            .map(Room::new)
+            .collect()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(input).to_string()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        Some(solve_2(input).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
 
     #[test]
     fn day_04_part_01_sample() {
-        let sample = vec![
-            "aaaaa-bbb-z-y-x-123[abxyz]",
-            "a-b-c-d-e-f-g-h-987[abcde]",
-            "not-a-real-room-404[oarel]",
-            "totally-real-room-200[decoy]",
-        ];
+        let sample = Day04::parse(
+            "aaaaa-bbb-z-y-x-123[abxyz]\n\
+             a-b-c-d-e-f-g-h-987[abcde]\n\
+             not-a-real-room-404[oarel]\n\
+             totally-real-room-200[decoy]",
+        )
+        .unwrap();
 
         assert_eq!(1_514, solve_1(&sample));
     }
 
     #[test]
     fn day_04_part_01_solution() {
-        let input = include_str!("../../inputs/day_04.txt")
-            .lines()
-            .collect_vec();
+        let input = Day04::parse(include_str!("../../../inputs/2016/day_04.txt")).unwrap();
 
         assert_eq!(245_102, solve_1(&input));
     }
@@ -131,9 +147,7 @@ mod tests {
 
     #[test]
     fn day_04_part_02_solution() {
-        let input = include_str!("../../inputs/day_04.txt")
-            .lines()
-            .collect_vec();
+        let input = Day04::parse(include_str!("../../../inputs/2016/day_04.txt")).unwrap();
 
         assert_eq!(324, solve_2(&input));
     }
@@ -4,6 +4,8 @@ use itertools::Itertools;
 use md5::{Digest, Md5};
 use rustc_hash::FxHashMap;
 
+use crate::solutions::{self, InputError, Solution};
+
 pub fn solve_1(door_id: &str) -> String {
     let mut index = 0u32;
     let mut solution = vec![];
@@ -55,6 +57,25 @@ pub fn solve_2(door_id: &str) -> String {
         .collect()
 }
 
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input<'a> = &'a str;
+    type Options = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        solutions::text(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        Some(solve_2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,7 +91,7 @@ mod tests {
     #[ignore = "slow brute force test"]
     #[test]
     fn day_05_part_01_solution() {
-        let input = include_str!("../../inputs/day_05.txt").trim();
+        let input = include_str!("../../../inputs/2016/day_05.txt").trim();
 
         assert_eq!("2414bc77", solve_1(input));
     }
@@ -86,7 +107,7 @@ mod tests {
     #[ignore = "slow brute force test"]
     #[test]
     fn day_05_part_02_solution() {
-        let input = include_str!("../../inputs/day_05.txt").trim();
+        let input = include_str!("../../../inputs/2016/day_05.txt").trim();
 
         assert_eq!("437e60fc", solve_2(input));
     }
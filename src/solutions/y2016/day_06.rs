@@ -1,5 +1,9 @@
+use std::ops::Not;
+
 use itertools::Itertools;
 
+use crate::solutions::{self, InputError, Solution};
+
 pub fn solve_1(messages: &[&str]) -> String {
     let bytes = messages.iter().map(|s| s.as_bytes()).collect_vec();
 
@@ -32,6 +36,37 @@ pub fn solve_2(messages: &[&str]) -> String {
         .collect()
 }
 
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input<'a> = Vec<&'a str>;
+    type Options = ();
+
+    /// The messages, which are all as long as the first one.
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        let messages = solutions::lines(input)?;
+
+        match messages
+            .iter()
+            .find(|m| m.is_ascii().not() || m.len() != messages[0].len())
+        {
+            Some(message) => Err(InputError::expected(
+                &format!("a message of {} letters", messages[0].len()),
+                message,
+            )),
+            None => Ok(messages),
+        }
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        Some(solve_2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use itertools::Itertools;
@@ -50,7 +85,7 @@ mod tests {
 
     #[test]
     fn day_06_part_01_solution() {
-        let input = include_str!("../../inputs/day_06.txt")
+        let input = include_str!("../../../inputs/2016/day_06.txt")
             .lines()
             .collect_vec();
 
@@ -69,7 +104,7 @@ mod tests {
 
     #[test]
     fn day_06_part_02_solution() {
-        let input = include_str!("../../inputs/day_06.txt")
+        let input = include_str!("../../../inputs/2016/day_06.txt")
             .lines()
             .collect_vec();
 
@@ -1,6 +1,8 @@
 use rustc_hash::FxHashSet;
 use std::ops::Not;
 
+use crate::solutions::{self, InputError, Solution};
+
 pub fn solve_1(ips: &[&str]) -> usize {
     ips.iter().filter(|ip| supports_tls(ip)).count()
 }
@@ -14,7 +16,7 @@ fn supports_tls(ip: &str) -> bool {
     let mut supports = false;
     let mut inside_hypernet = false;
 
-    for idx in 0..bytes.len() - 3 {
+    for idx in 0..bytes.len().saturating_sub(3) {
         match bytes[idx] {
             b'[' => inside_hypernet = true,
             b']' => inside_hypernet = false,
@@ -42,7 +44,7 @@ fn supports_sls(ip: &str) -> bool {
     let mut bab_s: FxHashSet<[u8; 2]> = FxHashSet::default();
     let mut inside_hypernet = false;
 
-    for idx in 0..bytes.len() - 2 {
+    for idx in 0..bytes.len().saturating_sub(2) {
         match bytes[idx] {
             b'[' => inside_hypernet = true,
             b']' => inside_hypernet = false,
@@ -61,6 +63,25 @@ fn supports_sls(ip: &str) -> bool {
     aba_s.is_disjoint(&bab_s).not()
 }
 
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input<'a> = Vec<&'a str>;
+    type Options = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        solutions::lines(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(input).to_string()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        Some(solve_2(input).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use itertools::Itertools;
@@ -81,7 +102,7 @@ mod tests {
 
     #[test]
     fn day_07_part_01_solution() {
-        let input = include_str!("../../inputs/day_07.txt")
+        let input = include_str!("../../../inputs/2016/day_07.txt")
             .lines()
             .collect_vec();
 
@@ -97,7 +118,7 @@ mod tests {
 
     #[test]
     fn day_07_part_02_solution() {
-        let input = include_str!("../../inputs/day_07.txt")
+        let input = include_str!("../../../inputs/2016/day_07.txt")
             .lines()
             .collect_vec();
 
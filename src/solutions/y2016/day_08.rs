@@ -1,12 +1,12 @@
-use std::str::FromStr;
-
 use itertools::Itertools;
 
-pub fn solve_1(instructions: &[&str], width: usize, height: usize) -> usize {
+use crate::solutions::{self, InputError, Solution};
+
+pub fn solve_1(instructions: &[Instruction], width: usize, height: usize) -> usize {
     display(instructions, width, height).voltage()
 }
 
-pub fn solve_2(instructions: &[&str], width: usize, height: usize) -> String {
+pub fn solve_2(instructions: &[Instruction], width: usize, height: usize) -> String {
     display(instructions, width, height)
         .pixels
         .iter()
@@ -21,13 +21,8 @@ pub fn solve_2(instructions: &[&str], width: usize, height: usize) -> String {
         .join("\n")
 }
 
-fn display(instructions: &[&str], width: usize, height: usize) -> Screen {
-    let instructions = instructions
-        .iter()
-        .map(|s| Instruction::new(s))
-        .collect_vec();
-
-    Screen::new(width, height).display(&instructions)
+fn display(instructions: &[Instruction], width: usize, height: usize) -> Screen {
+    Screen::new(width, height).display(instructions)
 }
 
 #[derive(Debug)]
@@ -93,77 +88,101 @@ impl Screen {
     }
 }
 
-#[derive(Debug)]
-enum Instruction {
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Instruction {
     Rect { width: usize, height: usize },
     RotateRow { row: usize, shift: usize },
     RotateCol { col: usize, shift: usize },
 }
 
 impl Instruction {
-    fn new(instruction: &str) -> Self {
-        match instruction {
-            i if instruction.starts_with("rect ") => {
-                let split = &i[5..]
-                    .split('x')
-                    .map(|s| usize::from_str(s).unwrap())
-                    .collect_vec();
-
-                Instruction::Rect {
-                    width: split[0],
-                    height: split[1],
-                }
-            }
-            i if i.starts_with("rotate row y=") => {
-                let split = &i[13..]
-                    .split(" by ")
-                    .map(|s| usize::from_str(s).unwrap())
-                    .collect_vec();
-
-                Instruction::RotateRow {
-                    row: split[0],
-                    shift: split[1],
-                }
-            }
-            i if i.starts_with("rotate column x=") => {
-                let split = &i[16..]
-                    .split(" by ")
-                    .map(|s| usize::from_str(s).unwrap())
-                    .collect_vec();
-
-                Instruction::RotateCol {
-                    col: split[0],
-                    shift: split[1],
-                }
-            }
-            _ => unreachable!(),
+    fn new(instruction: &str) -> Result<Self, InputError> {
+        let numbers = |operands: &str, separator: &str| {
+            let (a, b) = operands
+                .split_once(separator)
+                .ok_or_else(|| InputError::expected(EXPECTED, instruction))?;
+
+            Ok((
+                solutions::number(a, "a number")?,
+                solutions::number(b, "a number")?,
+            ))
+        };
+
+        if let Some(operands) = instruction.strip_prefix("rect ") {
+            let (width, height) = numbers(operands, "x")?;
+            Ok(Instruction::Rect { width, height })
+        } else if let Some(operands) = instruction.strip_prefix("rotate row y=") {
+            let (row, shift) = numbers(operands, " by ")?;
+            Ok(Instruction::RotateRow { row, shift })
+        } else if let Some(operands) = instruction.strip_prefix("rotate column x=") {
+            let (col, shift) = numbers(operands, " by ")?;
+            Ok(Instruction::RotateCol { col, shift })
+        } else {
+            Err(InputError::expected(EXPECTED, instruction))
+        }
+    }
+}
+
+const EXPECTED: &str = "`rect AxB`, `rotate row y=A by B` or `rotate column x=A by B`";
+
+/// The size of the screen.
+#[derive(Debug, Copy, Clone)]
+pub struct Options {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            width: 50,
+            height: 6,
         }
     }
 }
 
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input<'a> = Vec<Instruction>;
+    type Options = Options;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        solutions::lines(input)?
+            .into_iter()
+            .map(Instruction::new)
+            .collect()
+    }
+
+    fn part_1(input: &Self::Input<'_>, options: &Self::Options) -> String {
+        solve_1(input, options.width, options.height).to_string()
+    }
+
+    fn part_2(input: &Self::Input<'_>, options: &Self::Options) -> Option<String> {
+        Some(solve_2(input, options.width, options.height))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
 
     #[test]
     fn day_08_part_01_sample() {
-        let sample = vec![
-            "rect 3x2",
-            "rotate column x=1 by 1",
-            "rotate row y=0 by 4",
-            "rotate column x=1 by 1",
-        ];
+        let sample = Day08::parse(
+            "rect 3x2\n\
+             rotate column x=1 by 1\n\
+             rotate row y=0 by 4\n\
+             rotate column x=1 by 1",
+        )
+        .unwrap();
 
         assert_eq!(6, solve_1(&sample, 7, 3));
     }
 
     #[test]
     fn day_08_part_01_solution() {
-        let input = include_str!("../../inputs/day_08.txt")
-            .lines()
-            .collect_vec();
+        let input = Day08::parse(include_str!("../../../inputs/2016/day_08.txt")).unwrap();
 
         assert_eq!(110, solve_1(&input, 50, 6));
     }
@@ -175,9 +194,7 @@ mod tests {
 
     #[test]
     fn day_08_part_02_solution() {
-        let input = include_str!("../../inputs/day_08.txt")
-            .lines()
-            .collect_vec();
+        let input = Day08::parse(include_str!("../../../inputs/2016/day_08.txt")).unwrap();
         let expected = "\
             ####...##.#..#.###..#..#..##..###..#....#...#..##.\n\
             ...#....#.#..#.#..#.#.#..#..#.#..#.#....#...#...#.\n\
@@ -189,4 +206,16 @@ mod tests {
 
         assert_eq!(expected, solve_2(&input, 50, 6));
     }
+
+    #[test]
+    fn day_08_rejects_invalid_instructions() {
+        assert_eq!(
+            Err(InputError::expected(EXPECTED, "many pixels")),
+            Day08::parse("rect 3x2\nmany pixels")
+        );
+        assert_eq!(
+            Err(InputError::expected("a number", "two")),
+            Day08::parse("rotate row y=two by 4")
+        );
+    }
 }
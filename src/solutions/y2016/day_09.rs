@@ -1,3 +1,6 @@
+use std::ops::Not;
+
+use crate::solutions::{self, InputError, Solution};
 use crate::util::BASE_10;
 
 pub fn solve_1(data: &str) -> u128 {
@@ -63,6 +66,56 @@ fn decompress(data: &str, rec: bool) -> u128 {
     length
 }
 
+/// Checks that every marker, including those in the data they repeat, is like `(3x2)` and is
+/// followed by as many characters as it repeats.
+fn check_markers(data: &str) -> Result<(), InputError> {
+    let digits = |s: &str| s.is_empty().not() && s.bytes().all(|b| b.is_ascii_digit());
+    let mut rest = data;
+
+    while let Some(start) = rest.find('(') {
+        let marker = &rest[start..];
+        let (length, repeats) = marker
+            .find(')')
+            .and_then(|end| marker[1..end].split_once('x'))
+            .filter(|(length, repeats)| digits(length) && digits(repeats))
+            .ok_or_else(|| InputError::expected("a marker like `(3x2)`", marker))?;
+
+        let end = length.len() + repeats.len() + 3;
+        let length = solutions::number::<usize>(length, "a length")?;
+        solutions::number::<usize>(repeats, "a number of repeats")?;
+        let repeated = marker.get(end..end + length).ok_or_else(|| {
+            InputError::expected(&format!("{} characters to repeat", length), &marker[end..])
+        })?;
+
+        check_markers(repeated)?;
+        rest = &marker[end + length..];
+    }
+
+    Ok(())
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input<'a> = &'a str;
+    type Options = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        let data = solutions::text(input)?;
+        check_markers(data)?;
+
+        Ok(data)
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(input).to_string()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        Some(solve_2(input).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,7 +132,7 @@ mod tests {
 
     #[test]
     fn day_09_part_01_solution() {
-        let input = include_str!("../../inputs/day_09.txt").trim();
+        let input = Day09::parse(include_str!("../../../inputs/2016/day_09.txt")).unwrap();
 
         assert_eq!(70_186, solve_1(input));
     }
@@ -97,8 +150,21 @@ mod tests {
 
     #[test]
     fn day_09_part_02_solution() {
-        let input = include_str!("../../inputs/day_09.txt").trim();
+        let input = Day09::parse(include_str!("../../../inputs/2016/day_09.txt")).unwrap();
 
         assert_eq!(10_915_059_201, solve_2(input));
     }
+
+    #[test]
+    fn day_09_rejects_invalid_markers() {
+        assert_eq!(Ok("A(1x5)BC"), Day09::parse("A(1x5)BC\n"));
+        assert_eq!(
+            Err(InputError::expected("a marker like `(3x2)`", "(1y5)BC")),
+            Day09::parse("A(1y5)BC")
+        );
+        assert_eq!(
+            Err(InputError::expected("5 characters to repeat", "ABC")),
+            Day09::parse("(8x2)(5x2)ABC")
+        );
+    }
 }
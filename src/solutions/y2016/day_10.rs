@@ -0,0 +1,274 @@
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+use rustc_hash::FxHashMap;
+use std::collections::VecDeque;
+use std::ops::Not;
+
+use crate::solutions::{self, InputError, Solution};
+
+pub fn solve_1(instructions: &Instructions, seek_chips: [u32; 2]) -> u32 {
+    let Instructions { bots, chips } = instructions.clone();
+
+    Factory::new(bots, chips, seek_chips).run().0
+}
+
+pub fn solve_2(instructions: &Instructions, seek_chips: [u32; 2]) -> u32 {
+    let Instructions { bots, chips } = instructions.clone();
+
+    let out = Factory::new(bots, chips, seek_chips).run().1;
+    out[&0] * out[&1] * out[&2]
+}
+
+lazy_static! {
+    static ref RE_BOT: Regex = Regex::new(r"^bot (?<number>\d+) gives low to (?<dest_low>(:?bot|output) \d+) and high to (?<dest_high>(:?bot|output) \d+)$").unwrap();
+    static ref RE_CHIP: Regex = Regex::new(r"^value (?<value>\d+) goes to (?<dest>(:?bot|output) \d+)$").unwrap();
+}
+
+/// The bots by number, and the chips that go to them first.
+#[derive(Debug, Clone)]
+pub struct Instructions {
+    bots: FxHashMap<u32, Bot>,
+    chips: VecDeque<Chip>,
+}
+
+fn parse_instructions(instructions: &[&str]) -> Result<Instructions, InputError> {
+    let mut bots = FxHashMap::default();
+    let mut chips = VecDeque::new();
+
+    for instruction in instructions {
+        match instruction.starts_with("value") {
+            true => chips.push_back(Chip::new(instruction)?),
+            false => {
+                let bot = Bot::new(instruction)?;
+                bots.insert(bot.number, bot);
+            }
+        }
+    }
+
+    let destinations = bots
+        .values()
+        .flat_map(|b| [b.dest_low, b.dest_high])
+        .chain(chips.iter().map(|c| c.destination));
+    for destination in destinations {
+        if let Destination::DBot { number } = destination {
+            if bots.contains_key(&number).not() {
+                return Err(InputError::Invalid(format!(
+                    "bot {} gets chips, but has no instructions",
+                    number
+                )));
+            }
+        }
+    }
+
+    Ok(Instructions { bots, chips })
+}
+
+#[derive(Debug, Clone)]
+struct Factory {
+    bots: FxHashMap<u32, Bot>,
+    chips: VecDeque<Chip>,
+    seek_chips: [u32; 2],
+}
+
+impl Factory {
+    fn new(bots: FxHashMap<u32, Bot>, chips: VecDeque<Chip>, seek_chips: [u32; 2]) -> Self {
+        Self {
+            bots,
+            chips,
+            seek_chips,
+        }
+    }
+
+    fn run(&self) -> (u32, FxHashMap<u32, u32>) {
+        let mut fact = self.clone();
+        let mut chips_found = 0;
+        let mut outputs: FxHashMap<u32, u32> = FxHashMap::default();
+
+        while let Some(chip) = fact.chips.pop_front() {
+            match chip.destination {
+                Destination::DBot { number } => {
+                    let bot = fact.bots.get_mut(&number).unwrap();
+                    bot.chips.push(chip.value);
+
+                    if bot.chips.len() == 2 {
+                        let sorted_chips = bot.chips.iter().copied().sorted().collect_vec();
+
+                        if sorted_chips == fact.seek_chips {
+                            chips_found = bot.number;
+                        }
+
+                        bot.chips.clear();
+                        fact.chips.push_back(Chip {
+                            value: sorted_chips[0],
+                            destination: bot.dest_low,
+                        });
+                        fact.chips.push_back(Chip {
+                            value: sorted_chips[1],
+                            destination: bot.dest_high,
+                        });
+                    }
+                }
+                Destination::Output { number } => {
+                    outputs.insert(number, chip.value);
+                }
+            }
+        }
+
+        (chips_found, outputs)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Bot {
+    number: u32,
+    dest_low: Destination,
+    dest_high: Destination,
+    chips: Vec<u32>,
+}
+
+impl Bot {
+    fn new(instruction: &str) -> Result<Self, InputError> {
+        let caps = RE_BOT
+            .captures(instruction)
+            .ok_or_else(|| InputError::expected(EXPECTED, instruction))?;
+
+        let number = solutions::number(caps.name("number").unwrap().as_str(), "a bot")?;
+        let dest_low = Destination::new(caps.name("dest_low").unwrap().as_str())?;
+        let dest_high = Destination::new(caps.name("dest_high").unwrap().as_str())?;
+
+        Ok(Self {
+            number,
+            dest_low,
+            dest_high,
+            chips: vec![],
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Chip {
+    value: u32,
+    destination: Destination,
+}
+
+impl Chip {
+    fn new(instruction: &str) -> Result<Self, InputError> {
+        let caps = RE_CHIP
+            .captures(instruction)
+            .ok_or_else(|| InputError::expected(EXPECTED, instruction))?;
+
+        let value = solutions::number(caps.name("value").unwrap().as_str(), "a value")?;
+        let destination = Destination::new(caps.name("dest").unwrap().as_str())?;
+
+        Ok(Self { value, destination })
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Destination {
+    DBot { number: u32 },
+    Output { number: u32 },
+}
+
+impl Destination {
+    fn new(destination: &str) -> Result<Self, InputError> {
+        match &destination[0..3] {
+            "bot" => Ok(Destination::DBot {
+                number: solutions::number(&destination[4..], "a bot")?,
+            }),
+            "out" => Ok(Destination::Output {
+                number: solutions::number(&destination[7..], "an output")?,
+            }),
+            _ => unreachable!(),
+        }
+    }
+}
+
+const EXPECTED: &str = "`value A goes to bot B` or `bot A gives low to ... and high to ...`";
+
+/// The chips the bot to find compares.
+#[derive(Debug, Copy, Clone)]
+pub struct Options {
+    pub seek_chips: [u32; 2],
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            seek_chips: [17, 61],
+        }
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input<'a> = Instructions;
+    type Options = Options;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        parse_instructions(&solutions::lines(input)?)
+    }
+
+    fn part_1(input: &Self::Input<'_>, options: &Self::Options) -> String {
+        solve_1(input, options.seek_chips).to_string()
+    }
+
+    fn part_2(input: &Self::Input<'_>, options: &Self::Options) -> Option<String> {
+        Some(solve_2(input, options.seek_chips).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_10_part_01_sample() {
+        let sample = Day10::parse(
+            "value 5 goes to bot 2\n\
+             bot 2 gives low to bot 1 and high to bot 0\n\
+             value 3 goes to bot 1\n\
+             bot 1 gives low to output 1 and high to bot 0\n\
+             bot 0 gives low to output 2 and high to output 0\n\
+             value 2 goes to bot 2",
+        )
+        .unwrap();
+
+        assert_eq!(2, solve_1(&sample, [2, 5]));
+    }
+
+    #[test]
+    fn day_10_part_01_solution() {
+        let input = Day10::parse(include_str!("../../../inputs/2016/day_10.txt")).unwrap();
+
+        assert_eq!(161, solve_1(&input, [17, 61]));
+    }
+
+    #[test]
+    fn day_10_part_02_sample() {
+        // No sample inputs for part 2
+    }
+
+    #[test]
+    fn day_10_part_02_solution() {
+        let input = Day10::parse(include_str!("../../../inputs/2016/day_10.txt")).unwrap();
+
+        assert_eq!(133_163, solve_2(&input, [17, 61]));
+    }
+
+    #[test]
+    fn day_10_rejects_invalid_instructions() {
+        assert_eq!(
+            "bot 2 gets chips, but has no instructions",
+            Day10::parse("value 5 goes to bot 2")
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            InputError::expected(EXPECTED, "bot 2 gives everything away"),
+            Day10::parse("bot 2 gives everything away").unwrap_err()
+        );
+    }
+}
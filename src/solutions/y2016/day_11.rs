@@ -1,19 +1,22 @@
 use itertools::Itertools;
 use std::collections::VecDeque;
 use std::hash::Hash;
+use std::ops::Not;
 
 use regex::Regex;
 use rustc_hash::FxHashSet;
 
-pub fn solve_1(arrangement: &[&str]) -> u16 {
+use crate::solutions::{self, InputError, Solution};
+
+pub fn solve_1(arrangement: &[Floor]) -> u16 {
     solve(arrangement, false)
 }
 
-pub fn solve_2(arrangement: &[&str]) -> u16 {
+pub fn solve_2(arrangement: &[Floor]) -> u16 {
     solve(arrangement, true)
 }
 
-fn solve(arrangement: &[&str], extras: bool) -> u16 {
+fn solve(arrangement: &[Floor], extras: bool) -> u16 {
     let state = State::new(arrangement, extras);
 
     let mut visited: FxHashSet<SimpleState> = FxHashSet::default();
@@ -49,27 +52,8 @@ struct State<'a> {
 }
 
 impl<'a> State<'a> {
-    fn new(arrangement: &[&'a str], extras: bool) -> Self {
-        let re = Regex::new(r"(?<name>\w+)(?<kind> generator|-compatible microchip)").unwrap();
-        let mut floors = arrangement
-            .iter()
-            .map(|floor| {
-                re.captures_iter(floor)
-                    .map(|caps| {
-                        let name = caps.name("name").unwrap().as_str();
-                        let kind = caps.name("kind").unwrap().as_str();
-                        let kind = match &kind[kind.len() - 9..] {
-                            "generator" => Kind::Generator,
-                            "microchip" => Kind::Microchip,
-                            _ => unreachable!(),
-                        };
-
-                        Item { name, kind }
-                    })
-                    .collect()
-            })
-            .map(|items| Floor { items })
-            .collect_vec();
+    fn new(arrangement: &[Floor<'a>], extras: bool) -> Self {
+        let mut floors = arrangement.to_vec();
 
         if extras {
             [
@@ -167,11 +151,31 @@ struct SimpleState {
 }
 
 #[derive(Debug, Clone)]
-struct Floor<'a> {
+pub struct Floor<'a> {
     items: FxHashSet<Item<'a>>,
 }
 
 impl<'a> Floor<'a> {
+    fn new(floor: &'a str) -> Self {
+        let re = Regex::new(r"(?<name>\w+)(?<kind> generator|-compatible microchip)").unwrap();
+        let items = re
+            .captures_iter(floor)
+            .map(|caps| {
+                let name = caps.name("name").unwrap().as_str();
+                let kind = caps.name("kind").unwrap().as_str();
+                let kind = match &kind[kind.len() - 9..] {
+                    "generator" => Kind::Generator,
+                    "microchip" => Kind::Microchip,
+                    _ => unreachable!(),
+                };
+
+                Item { name, kind }
+            })
+            .collect();
+
+        Self { items }
+    }
+
     fn allowed(&self) -> bool {
         self.items.is_empty()
             || self.items.iter().all(|i| i.kind == Kind::Microchip)
@@ -222,29 +226,74 @@ impl FloorNr {
     }
 }
 
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = Vec<Floor<'a>>;
+    type Options = ();
+
+    /// The four floors, with a generator for every microchip and the other way around.
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        let floors = solutions::lines(input)?
+            .into_iter()
+            .map(Floor::new)
+            .collect_vec();
+        if floors.len() != 4 {
+            return Err(InputError::Invalid(format!(
+                "expected 4 floors, found {}",
+                floors.len()
+            )));
+        }
+
+        let items = floors
+            .iter()
+            .flat_map(|f| &f.items)
+            .collect::<FxHashSet<_>>();
+        for item in &items {
+            let (kind, found, missing) = match item.kind {
+                Kind::Generator => (Kind::Microchip, "generator", "microchip"),
+                Kind::Microchip => (Kind::Generator, "microchip", "generator"),
+            };
+            if items.contains(&Item { kind, ..**item }).not() {
+                return Err(InputError::Invalid(format!(
+                    "the {} {} has no {} to go with it",
+                    item.name, found, missing
+                )));
+            }
+        }
+
+        Ok(floors)
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(input).to_string()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        Some(solve_2(input).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
 
     #[test]
     fn day_11_part_01_sample() {
-        let sample = vec![
-            "The first floor contains a hydrogen-compatible microchip and a lithium-compatible microchip.",
-            "The second floor contains a hydrogen generator.",
-            "The third floor contains a lithium generator.",
-            "The fourth floor contains nothing relevant.",
-        ];
+        let sample = Day11::parse(
+            "The first floor contains a hydrogen-compatible microchip and a lithium-compatible microchip.\n\
+             The second floor contains a hydrogen generator.\n\
+             The third floor contains a lithium generator.\n\
+             The fourth floor contains nothing relevant.",
+        )
+        .unwrap();
 
         assert_eq!(11, solve_1(&sample));
     }
 
     #[test]
     fn day_11_part_01_solution() {
-        let input = include_str!("../../inputs/day_11.txt")
-            .lines()
-            .collect_vec();
+        let input = Day11::parse(include_str!("../../../inputs/2016/day_11.txt")).unwrap();
 
         assert_eq!(33, solve_1(&input));
     }
@@ -256,10 +305,29 @@ mod tests {
 
     #[test]
     fn day_11_part_02_solution() {
-        let input = include_str!("../../inputs/day_11.txt")
-            .lines()
-            .collect_vec();
+        let input = Day11::parse(include_str!("../../../inputs/2016/day_11.txt")).unwrap();
 
         assert_eq!(57, solve_2(&input));
     }
+
+    #[test]
+    fn day_11_rejects_invalid_arrangements() {
+        assert_eq!(
+            "expected 4 floors, found 1",
+            Day11::parse("The first floor contains a hydrogen generator.")
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "the lithium generator has no microchip to go with it",
+            Day11::parse(
+                "The first floor contains a lithium generator.\n\
+                 The second floor contains nothing relevant.\n\
+                 The third floor contains nothing relevant.\n\
+                 The fourth floor contains nothing relevant."
+            )
+            .unwrap_err()
+            .to_string()
+        );
+    }
 }
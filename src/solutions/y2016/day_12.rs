@@ -0,0 +1,77 @@
+use crate::computer::{Computer, Register};
+use crate::solutions::{self, InputError, Solution};
+
+pub fn solve_1(code: &[&str]) -> i64 {
+    Computer::new(code, vec![])
+        .unwrap()
+        .run(true, 0)
+        .unwrap()
+        .registers[&Register::A]
+}
+
+pub fn solve_2(code: &[&str]) -> i64 {
+    Computer::new(code, vec![(Register::C, 1)])
+        .unwrap()
+        .run(true, 0)
+        .unwrap()
+        .registers[&Register::A]
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input<'a> = Vec<&'a str>;
+    type Options = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        let code = solutions::lines(input)?;
+        Computer::new(&code, vec![]).map_err(|e| InputError::Invalid(e.to_string()))?;
+
+        Ok(code)
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(input).to_string()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        Some(solve_2(input).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn day_12_part_01_sample() {
+        let sample = vec!["cpy 41 a", "inc a", "inc a", "dec a", "jnz a 2", "dec a"];
+
+        assert_eq!(42, solve_1(&sample));
+    }
+
+    #[test]
+    fn day_12_part_01_solution() {
+        let input = include_str!("../../../inputs/2016/day_12.txt")
+            .lines()
+            .collect_vec();
+
+        assert_eq!(318_009, solve_1(&input));
+    }
+
+    #[test]
+    fn day_12_part_02_sample() {
+        // No sample inputs for part 2
+    }
+
+    #[test]
+    fn day_12_part_02_solution() {
+        let input = include_str!("../../../inputs/2016/day_12.txt")
+            .lines()
+            .collect_vec();
+
+        assert_eq!(9_227_663, solve_2(&input));
+    }
+}
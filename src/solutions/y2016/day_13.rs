@@ -1,19 +1,18 @@
 use rustc_hash::FxHashSet;
 use std::collections::VecDeque;
 use std::ops::Not;
-use std::str::FromStr;
 
-pub fn solve_1(favourite: &str, dest_x: i32, dest_y: i32) -> u32 {
+use crate::solutions::{self, InputError, Solution};
+
+pub fn solve_1(favourite: i32, dest_x: i32, dest_y: i32) -> u32 {
     solve(favourite, dest_x, dest_y, true)
 }
 
-pub fn solve_2(favourite: &str) -> u32 {
+pub fn solve_2(favourite: i32) -> u32 {
     solve(favourite, 0, 0, false)
 }
 
-fn solve(favourite: &str, dest_x: i32, dest_y: i32, find_dest: bool) -> u32 {
-    let favourite = i32::from_str(favourite).unwrap();
-
+fn solve(favourite: i32, dest_x: i32, dest_y: i32, find_dest: bool) -> u32 {
     let start = Coordinate { x: 1, y: 1 };
     let destination = Coordinate {
         x: dest_x,
@@ -88,20 +87,53 @@ impl Coordinate {
     }
 }
 
+/// The location to reach in part 1.
+#[derive(Debug, Copy, Clone)]
+pub struct Options {
+    pub destination: (i32, i32),
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            destination: (31, 39),
+        }
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input<'a> = i32;
+    type Options = Options;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        solutions::number(solutions::text(input)?, "the favourite number")
+    }
+
+    fn part_1(input: &Self::Input<'_>, options: &Self::Options) -> String {
+        solve_1(*input, options.destination.0, options.destination.1).to_string()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        Some(solve_2(*input).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn day_13_part_01_sample() {
-        let sample = "10";
+        let sample = 10;
 
         assert_eq!(11, solve_1(sample, 7, 4));
     }
 
     #[test]
     fn day_13_part_01_solution() {
-        let input = include_str!("../../inputs/day_13.txt").trim();
+        let input = Day13::parse(include_str!("../../../inputs/2016/day_13.txt")).unwrap();
 
         assert_eq!(86, solve_1(input, 31, 39));
     }
@@ -113,7 +145,7 @@ mod tests {
 
     #[test]
     fn day_13_part_02_solution() {
-        let input = include_str!("../../inputs/day_13.txt").trim();
+        let input = Day13::parse(include_str!("../../../inputs/2016/day_13.txt")).unwrap();
 
         assert_eq!(127, solve_2(input));
     }
@@ -2,6 +2,8 @@ use itertools::Itertools;
 use md5::{Digest, Md5};
 use rustc_hash::{FxHashMap, FxHashSet};
 
+use crate::solutions::{self, InputError, Solution};
+
 pub fn solve_1(salt: &str) -> usize {
     solve(salt, &Hash::single)
 }
@@ -92,6 +94,25 @@ impl Hash {
     }
 }
 
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input<'a> = &'a str;
+    type Options = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        solutions::text(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(input).to_string()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        Some(solve_2(input).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,7 +126,7 @@ mod tests {
 
     #[test]
     fn day_14_part_01_solution() {
-        let input = include_str!("../../inputs/day_14.txt").trim();
+        let input = include_str!("../../../inputs/2016/day_14.txt").trim();
 
         assert_eq!(15_168, solve_1(input));
     }
@@ -121,7 +142,7 @@ mod tests {
     #[ignore = "slow brute force test"]
     #[test]
     fn day_14_part_02_solution() {
-        let input = include_str!("../../inputs/day_14.txt").trim();
+        let input = include_str!("../../../inputs/2016/day_14.txt").trim();
 
         assert_eq!(20_864, solve_2(input));
     }
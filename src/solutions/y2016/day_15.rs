@@ -0,0 +1,121 @@
+use regex::Regex;
+
+use crate::solutions::{self, InputError, Solution};
+
+pub fn solve_1(disks: &[Disk]) -> u32 {
+    solve(disks, false)
+}
+
+pub fn solve_2(disks: &[Disk]) -> u32 {
+    solve(disks, true)
+}
+
+fn solve(disks: &[Disk], extra_disk: bool) -> u32 {
+    let mut disks = disks.to_vec();
+    if extra_disk {
+        disks.push(Disk {
+            positions: 11,
+            offset: 0,
+        });
+    }
+    let mut time = 0;
+
+    loop {
+        let all_zero = disks
+            .iter()
+            .enumerate()
+            .all(|(idx, d)| d.position(time + idx as u32 + 1) == 0);
+
+        if all_zero {
+            return time;
+        }
+
+        time += 1
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Disk {
+    positions: u32,
+    offset: u32,
+}
+
+impl Disk {
+    fn position(&self, time: u32) -> u32 {
+        (time + self.offset) % self.positions
+    }
+}
+
+impl Disk {
+    fn new(disk: &str) -> Result<Self, InputError> {
+        let re = Regex::new(r"^Disc #(:?\d+) has (?<positions>[1-9]\d*) positions; at time=0, it is at position (?<offset>\d+)\.$").unwrap();
+        let caps = re.captures(disk).ok_or_else(|| {
+            InputError::expected(
+                "a disc like `Disc #1 has 5 positions; at time=0, it is at position 4.`",
+                disk,
+            )
+        })?;
+
+        let positions = solutions::number(caps.name("positions").unwrap().as_str(), "positions")?;
+        let offset = solutions::number(caps.name("offset").unwrap().as_str(), "a position")?;
+
+        Ok(Self { positions, offset })
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input<'a> = Vec<Disk>;
+    type Options = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        solutions::lines(input)?
+            .into_iter()
+            .map(Disk::new)
+            .collect()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(input).to_string()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        Some(solve_2(input).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_15_part_01_sample() {
+        let sample = Day15::parse(
+            "Disc #1 has 5 positions; at time=0, it is at position 4.\n\
+             Disc #2 has 2 positions; at time=0, it is at position 1.",
+        )
+        .unwrap();
+
+        assert_eq!(5, solve_1(&sample));
+    }
+
+    #[test]
+    fn day_15_part_01_solution() {
+        let input = Day15::parse(include_str!("../../../inputs/2016/day_15.txt")).unwrap();
+
+        assert_eq!(122_318, solve_1(&input));
+    }
+
+    #[test]
+    fn day_15_part_02_sample() {
+        // No sample inputs for part 2
+    }
+
+    #[test]
+    fn day_15_part_02_solution() {
+        let input = Day15::parse(include_str!("../../../inputs/2016/day_15.txt")).unwrap();
+
+        assert_eq!(3_208_583, solve_2(&input));
+    }
+}
@@ -1,5 +1,7 @@
 use itertools::Itertools;
 
+use crate::solutions::{self, InputError, Solution};
+
 pub fn solve(data: &str, disk_size: usize) -> String {
     DragonCurve::new(data).enlarge(disk_size).checksum()
 }
@@ -61,6 +63,44 @@ impl DragonCurve {
     }
 }
 
+/// The sizes of the disks to fill in both parts.
+#[derive(Debug, Copy, Clone)]
+pub struct Options {
+    pub disk_sizes: [usize; 2],
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            disk_sizes: [272, 35_651_584],
+        }
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input<'a> = &'a str;
+    type Options = Options;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        let data = solutions::text(input)?;
+
+        match data.chars().all(|c| c == '0' || c == '1') {
+            true => Ok(data),
+            false => Err(InputError::expected("`0`s and `1`s", data)),
+        }
+    }
+
+    fn part_1(input: &Self::Input<'_>, options: &Self::Options) -> String {
+        solve(input, options.disk_sizes[0])
+    }
+
+    fn part_2(input: &Self::Input<'_>, options: &Self::Options) -> Option<String> {
+        Some(solve(input, options.disk_sizes[1]))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,7 +113,7 @@ mod tests {
 
     #[test]
     fn day_16_part_01_solution() {
-        let input = include_str!("../../inputs/day_16.txt").trim();
+        let input = include_str!("../../../inputs/2016/day_16.txt").trim();
 
         assert_eq!("10101001010100001", solve(input, 272));
     }
@@ -86,7 +126,7 @@ mod tests {
     #[ignore = "slow brute force test"]
     #[test]
     fn day_16_part_02_solution() {
-        let input = include_str!("../../inputs/day_16.txt").trim();
+        let input = include_str!("../../../inputs/2016/day_16.txt").trim();
 
         assert_eq!("10100001110101001", solve(input, 35_651_584));
     }
@@ -1,6 +1,8 @@
 use md5::{Digest, Md5};
 use std::collections::VecDeque;
 
+use crate::solutions::{self, InputError, Solution};
+
 pub fn solve_1(passcode: &str) -> String {
     solve(passcode, Path::Shortest)
 }
@@ -140,6 +142,25 @@ struct Coordinate {
     y: u8,
 }
 
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input<'a> = &'a str;
+    type Options = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        solutions::text(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        Some(solve_2(input).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,7 +174,7 @@ mod tests {
 
     #[test]
     fn day_17_part_01_solution() {
-        let input = include_str!("../../inputs/day_17.txt").trim();
+        let input = include_str!("../../../inputs/2016/day_17.txt").trim();
 
         assert_eq!("RDRDUDLRDR", solve_1(input));
     }
@@ -167,7 +188,7 @@ mod tests {
 
     #[test]
     fn day_17_part_02_solution() {
-        let input = include_str!("../../inputs/day_17.txt").trim();
+        let input = include_str!("../../../inputs/2016/day_17.txt").trim();
 
         assert_eq!(386, solve_2(input));
     }
@@ -1,5 +1,7 @@
-pub fn solve(row: &str, nr_rows: u32) -> usize {
-    let mut row = Row::new(row);
+use crate::solutions::{self, InputError, Solution};
+
+pub fn solve(row: &Row, nr_rows: u32) -> usize {
+    let mut row = row.clone();
     let mut rows = vec![row.clone()];
 
     for _ in 1..nr_rows {
@@ -13,15 +15,15 @@ pub fn solve(row: &str, nr_rows: u32) -> usize {
 }
 
 #[derive(Debug, Clone)]
-struct Row {
+pub struct Row {
     tiles: Vec<Tile>,
 }
 
 impl Row {
-    fn new(row: &str) -> Self {
-        Self {
-            tiles: row.chars().map(Tile::new).collect(),
-        }
+    fn new(row: &str) -> Result<Self, InputError> {
+        Ok(Self {
+            tiles: row.chars().map(Tile::new).collect::<Result<_, _>>()?,
+        })
     }
 
     fn next(&self) -> Self {
@@ -60,30 +62,63 @@ enum Tile {
 }
 
 impl Tile {
-    fn new(tile: char) -> Self {
+    fn new(tile: char) -> Result<Self, InputError> {
         match tile {
-            '.' => Tile::Safe,
-            '^' => Tile::Trap,
-            _ => unreachable!(),
+            '.' => Ok(Tile::Safe),
+            '^' => Ok(Tile::Trap),
+            _ => Err(InputError::expected("`.` or `^`", &tile.to_string())),
+        }
+    }
+}
+
+/// The number of rows in both parts.
+#[derive(Debug, Copy, Clone)]
+pub struct Options {
+    pub nr_rows: [u32; 2],
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            nr_rows: [40, 400_000],
         }
     }
 }
 
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input<'a> = Row;
+    type Options = Options;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        Row::new(solutions::text(input)?)
+    }
+
+    fn part_1(input: &Self::Input<'_>, options: &Self::Options) -> String {
+        solve(input, options.nr_rows[0]).to_string()
+    }
+
+    fn part_2(input: &Self::Input<'_>, options: &Self::Options) -> Option<String> {
+        Some(solve(input, options.nr_rows[1]).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn day_18_part_01_sample() {
-        assert_eq!(6, solve("..^^.", 3));
-        assert_eq!(38, solve(".^^.^.^^^^", 10));
+        assert_eq!(6, solve(&Day18::parse("..^^.").unwrap(), 3));
+        assert_eq!(38, solve(&Day18::parse(".^^.^.^^^^").unwrap(), 10));
     }
 
     #[test]
     fn day_18_part_01_solution() {
-        let input = include_str!("../../inputs/day_18.txt").trim();
+        let input = Day18::parse(include_str!("../../../inputs/2016/day_18.txt")).unwrap();
 
-        assert_eq!(1_951, solve(input, 40));
+        assert_eq!(1_951, solve(&input, 40));
     }
 
     #[test]
@@ -93,8 +128,8 @@ mod tests {
 
     #[test]
     fn day_18_part_02_solution() {
-        let input = include_str!("../../inputs/day_18.txt").trim();
+        let input = Day18::parse(include_str!("../../../inputs/2016/day_18.txt")).unwrap();
 
-        assert_eq!(20_002_936, solve(input, 400_000));
+        assert_eq!(20_002_936, solve(&input, 400_000));
     }
 }
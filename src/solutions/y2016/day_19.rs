@@ -1,3 +1,5 @@
+use crate::solutions::{self, InputError, Solution};
+
 // Formula worked out on paper, found the sequence going:
 // 1  1 3  1 3 5 7  1 3 5 7 9 11 13 15  1 ...
 // i.e. a row of rows off all odd numbers, increasing in length by power of 2
@@ -45,6 +47,25 @@ pub fn solve_2(nr_elves: u32) -> u32 {
     }
 }
 
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input<'a> = u32;
+    type Options = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        solutions::number(solutions::text(input)?, "the number of elves")
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(*input).to_string()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        Some(solve_2(*input).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -58,7 +79,7 @@ mod tests {
 
     #[test]
     fn day_19_part_01_solution() {
-        let input = u32::from_str(include_str!("../../inputs/day_19.txt").trim()).unwrap();
+        let input = u32::from_str(include_str!("../../../inputs/2016/day_19.txt").trim()).unwrap();
 
         assert_eq!(1_816_277, solve_1(input));
     }
@@ -70,7 +91,7 @@ mod tests {
 
     #[test]
     fn day_19_part_02_solution() {
-        let input = u32::from_str(include_str!("../../inputs/day_19.txt").trim()).unwrap();
+        let input = u32::from_str(include_str!("../../../inputs/2016/day_19.txt").trim()).unwrap();
 
         assert_eq!(1_410_967, solve_2(input));
     }
@@ -0,0 +1,115 @@
+use itertools::Itertools;
+
+use crate::solutions::{self, InputError, Solution};
+
+pub fn solve_1(blacklist: &[Range]) -> u64 {
+    let ranges = blacklist.iter().sorted().collect_vec();
+
+    let mut lowest_range = Range { start: 0, end: 0 };
+
+    for range in ranges.iter() {
+        if range.start > lowest_range.end + 1 {
+            return lowest_range.end + 1;
+        }
+
+        lowest_range.end = lowest_range.end.max(range.end)
+    }
+
+    unreachable!()
+}
+
+pub fn solve_2(blacklist: &[Range]) -> u64 {
+    let ranges = blacklist.iter().sorted().collect_vec();
+
+    let mut current = Range { start: 0, end: 0 };
+    let mut merged_ranges = vec![];
+
+    for &range in ranges.into_iter() {
+        if range.start > current.end + 1 {
+            merged_ranges.push(current);
+            current = range
+        } else {
+            current.end = current.end.max(range.end)
+        }
+    }
+    merged_ranges.push(current);
+
+    u32::MAX as u64 - merged_ranges.iter().map(|r| r.len()).sum::<u64>() + 1
+}
+
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Range {
+    start: u64,
+    end: u64,
+}
+
+impl Range {
+    fn new(range: &str) -> Result<Self, InputError> {
+        let expected = || InputError::expected("a range of IPs like `5-8`", range);
+        let (start, end) = range.split_once('-').ok_or_else(expected)?;
+        let start = solutions::number::<u32>(start, "an IP")? as u64;
+        let end = solutions::number::<u32>(end, "an IP")? as u64;
+
+        match start <= end {
+            true => Ok(Self { start, end }),
+            false => Err(expected()),
+        }
+    }
+
+    fn len(&self) -> u64 {
+        self.end - self.start + 1
+    }
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input<'a> = Vec<Range>;
+    type Options = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        solutions::lines(input)?
+            .into_iter()
+            .map(Range::new)
+            .collect()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(input).to_string()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        Some(solve_2(input).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_20_part_01_sample() {
+        let sample = Day20::parse("5-8\n0-2\n4-7").unwrap();
+
+        assert_eq!(3, solve_1(&sample));
+    }
+
+    #[test]
+    fn day_20_part_01_solution() {
+        let input = Day20::parse(include_str!("../../../inputs/2016/day_20.txt")).unwrap();
+
+        assert_eq!(14_975_795, solve_1(&input));
+    }
+
+    #[test]
+    fn day_20_part_02_sample() {
+        // No sample inputs for part 2
+    }
+
+    #[test]
+    fn day_20_part_02_solution() {
+        let input = Day20::parse(include_str!("../../../inputs/2016/day_20.txt")).unwrap();
+
+        assert_eq!(101, solve_2(&input));
+    }
+}
@@ -3,28 +3,22 @@ use lazy_static::lazy_static;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
-use regex::Regex;
+use regex::{Captures, Regex};
 use rustc_hash::FxHashMap;
 
-pub fn solve_1(instructions: &[&str], password: &str) -> String {
-    let instructions = instructions
-        .iter()
-        .map(|s| Instruction::new(s))
-        .collect_vec();
+use crate::solutions::{self, InputError, Solution};
+
+pub fn solve_1(instructions: &[Instruction], password: &str) -> String {
     let mut password = Password::new(password);
 
-    for ins in &instructions {
+    for ins in instructions {
         ins.run(&mut password);
     }
 
     password.to_string()
 }
 
-pub fn solve_2(instructions: &[&str], to_find: &str) -> String {
-    let instructions = instructions
-        .iter()
-        .map(|s| Instruction::new(s))
-        .collect_vec();
+pub fn solve_2(instructions: &[Instruction], to_find: &str) -> String {
     let mut password = Password::new(to_find);
     let rot_pos_mapping: FxHashMap<usize, usize> = (0..to_find.len())
         .map(|idx| {
@@ -43,7 +37,7 @@ pub fn solve_2(instructions: &[&str], to_find: &str) -> String {
 }
 
 #[derive(Debug)]
-enum Instruction {
+pub enum Instruction {
     SwapPosition { x: usize, y: usize },
     SwapLetter { x: char, y: char },
     RotateSteps { rotation: Rotation, steps: usize },
@@ -53,44 +47,38 @@ enum Instruction {
 }
 
 impl Instruction {
-    fn new(instruction: &str) -> Self {
-        match &instruction[0..8] {
-            "swap pos" => {
-                let caps = RE_SWAP_POS.captures(instruction).unwrap();
-                let x = usize::from_str(caps.name("pos_x").unwrap().as_str()).unwrap();
-                let y = usize::from_str(caps.name("pos_y").unwrap().as_str()).unwrap();
-                Instruction::SwapPosition { x, y }
-            }
-            "swap let" => {
-                let caps = RE_SWAP_LET.captures(instruction).unwrap();
-                let x = caps.name("let_x").unwrap().as_str().chars().next().unwrap();
-                let y = caps.name("let_y").unwrap().as_str().chars().next().unwrap();
-                Instruction::SwapLetter { x, y }
-            }
-            "rotate l" | "rotate r" => {
-                let caps = RE_ROT_STEPS.captures(instruction).unwrap();
-                let rotation = Rotation::new(caps.name("rot").unwrap().as_str());
-                let steps = usize::from_str(caps.name("steps").unwrap().as_str()).unwrap();
-                Instruction::RotateSteps { rotation, steps }
-            }
-            "rotate b" => {
-                let caps = RE_ROT_LET.captures(instruction).unwrap();
-                let x = caps.name("let").unwrap().as_str().chars().next().unwrap();
-                Instruction::RotateLetter { x }
-            }
-            "reverse " => {
-                let caps = RE_REV_POS.captures(instruction).unwrap();
-                let x = usize::from_str(caps.name("pos_x").unwrap().as_str()).unwrap();
-                let y = usize::from_str(caps.name("pos_y").unwrap().as_str()).unwrap();
-                Instruction::ReversePosition { x, y }
-            }
-            "move pos" => {
-                let caps = RE_MOV_POS.captures(instruction).unwrap();
-                let x = usize::from_str(caps.name("pos_x").unwrap().as_str()).unwrap();
-                let y = usize::from_str(caps.name("pos_y").unwrap().as_str()).unwrap();
-                Instruction::MovePosition { x, y }
-            }
-            _ => unreachable!(),
+    fn new(instruction: &str) -> Result<Self, InputError> {
+        let position = |caps: &Captures, name: &str| usize::from_str(&caps[name]).unwrap();
+        let letter = |caps: &Captures, name: &str| caps[name].chars().next().unwrap();
+
+        if let Some(caps) = RE_SWAP_POS.captures(instruction) {
+            let x = position(&caps, "pos_x");
+            let y = position(&caps, "pos_y");
+            Ok(Instruction::SwapPosition { x, y })
+        } else if let Some(caps) = RE_SWAP_LET.captures(instruction) {
+            let x = letter(&caps, "let_x");
+            let y = letter(&caps, "let_y");
+            Ok(Instruction::SwapLetter { x, y })
+        } else if let Some(caps) = RE_ROT_STEPS.captures(instruction) {
+            let rotation = Rotation::new(&caps["rot"]);
+            let steps = solutions::number(&caps["steps"], "a number of steps")?;
+            Ok(Instruction::RotateSteps { rotation, steps })
+        } else if let Some(caps) = RE_ROT_LET.captures(instruction) {
+            let x = letter(&caps, "let");
+            Ok(Instruction::RotateLetter { x })
+        } else if let Some(caps) = RE_REV_POS.captures(instruction) {
+            let x = position(&caps, "pos_x");
+            let y = position(&caps, "pos_y");
+            Ok(Instruction::ReversePosition { x, y })
+        } else if let Some(caps) = RE_MOV_POS.captures(instruction) {
+            let x = position(&caps, "pos_x");
+            let y = position(&caps, "pos_y");
+            Ok(Instruction::MovePosition { x, y })
+        } else {
+            Err(InputError::expected(
+                "a swap, rotate, reverse or move",
+                instruction,
+            ))
         }
     }
 
@@ -226,7 +214,7 @@ impl Display for Password {
 }
 
 #[derive(Debug)]
-enum Rotation {
+pub enum Rotation {
     Left,
     Right,
 }
@@ -256,33 +244,68 @@ lazy_static! {
         Regex::new(r"^move position (?<pos_x>\d) to position (?<pos_y>\d)$").unwrap();
 }
 
+/// The password to scramble in part 1, and the scrambled one to unscramble in part 2.
+#[derive(Debug, Copy, Clone)]
+pub struct Options {
+    pub password: &'static str,
+    pub scrambled: &'static str,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            password: "abcdefgh",
+            scrambled: "fbgdceah",
+        }
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input<'a> = Vec<Instruction>;
+    type Options = Options;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        solutions::lines(input)?
+            .into_iter()
+            .map(Instruction::new)
+            .collect()
+    }
+
+    fn part_1(input: &Self::Input<'_>, options: &Self::Options) -> String {
+        solve_1(input, options.password)
+    }
+
+    fn part_2(input: &Self::Input<'_>, options: &Self::Options) -> Option<String> {
+        Some(solve_2(input, options.scrambled))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
 
     #[test]
     fn day_21_part_01_sample() {
-        let sample = vec![
-            "swap position 4 with position 0",
-            "swap letter d with letter b",
-            "reverse positions 0 through 4",
-            "rotate left 1 step",
-            "move position 1 to position 4",
-            "move position 3 to position 0",
-            "rotate based on position of letter b",
-            "rotate based on position of letter d",
-        ];
+        let sample = Day21::parse(
+            "swap position 4 with position 0\n\
+             swap letter d with letter b\n\
+             reverse positions 0 through 4\n\
+             rotate left 1 step\n\
+             move position 1 to position 4\n\
+             move position 3 to position 0\n\
+             rotate based on position of letter b\n\
+             rotate based on position of letter d",
+        )
+        .unwrap();
 
         assert_eq!("decab", solve_1(&sample, "abcde"));
     }
 
     #[test]
     fn day_21_part_01_solution() {
-        let input = include_str!("../../inputs/day_21.txt")
-            .lines()
-            .collect_vec();
+        let input = Day21::parse(include_str!("../../../inputs/2016/day_21.txt")).unwrap();
 
         assert_eq!("bdfhgeca", solve_1(&input, "abcdefgh"));
     }
@@ -294,9 +317,7 @@ mod tests {
 
     #[test]
     fn day_21_part_02_solution() {
-        let input = include_str!("../../inputs/day_21.txt")
-            .lines()
-            .collect_vec();
+        let input = Day21::parse(include_str!("../../../inputs/2016/day_21.txt")).unwrap();
 
         assert_eq!("gdfcabeh", solve_2(&input, "fbgdceah"));
     }
@@ -1,27 +1,29 @@
 use itertools::Itertools;
 use std::fmt::{Display, Formatter};
-use std::str::FromStr;
 
 use regex::Regex;
 use rustc_hash::{FxHashMap, FxHashSet};
 
-pub fn solve_1(nodes: &[&str]) -> usize {
-    Grid::new(nodes).find_pairs().len()
+use crate::solutions::{self, InputError, Solution};
+
+pub fn solve_1(grid: &Grid) -> usize {
+    grid.find_pairs().len()
 }
 
-pub fn solve_2(nodes: &[&str], test_input: bool) -> u32 {
-    Grid::new(nodes).move_data(test_input)
+pub fn solve_2(grid: &Grid, test_input: bool) -> u32 {
+    grid.move_data(test_input)
 }
 
 #[derive(Debug)]
-struct Grid {
+pub struct Grid {
     nodes: FxHashMap<Coordinate, Node>,
     max_x: i32,
     max_y: i32,
 }
 
 impl Grid {
-    fn new(nodes: &[&str]) -> Self {
+    /// The grid from the output of `df`, with its two header lines.
+    fn new(nodes: &[&str]) -> Result<Self, InputError> {
         let re = Regex::new(
             r"^/dev/grid/node-x(?<x>\d+)-y(?<y>\d+) +\d+T +(?<used>\d+)+T +(?<avail>\d+)T +\d+%$",
         )
@@ -30,16 +32,22 @@ impl Grid {
             .iter()
             .skip(2)
             .map(|s| Node::new(s, &re))
-            .map(|n| (n.coord, n))
-            .collect();
-        let max_x = nodes.keys().max_by_key(|c| c.x).unwrap().x;
-        let max_y = nodes.keys().max_by_key(|c| c.y).unwrap().y;
+            .map_ok(|n| (n.coord, n))
+            .collect::<Result<_, _>>()?;
+        let (Some(max_x), Some(max_y)) = (
+            nodes.keys().map(|c| c.x).max(),
+            nodes.keys().map(|c| c.y).max(),
+        ) else {
+            return Err(InputError::Invalid(
+                "expected nodes after the two header lines".to_string(),
+            ));
+        };
 
-        Self {
+        Ok(Self {
             nodes,
             max_x,
             max_y,
-        }
+        })
     }
 
     fn find_pairs(&self) -> Vec<(Node, Node)> {
@@ -106,19 +114,21 @@ struct Node {
 }
 
 impl Node {
-    fn new(node: &str, re: &Regex) -> Self {
-        let caps = re.captures(node).unwrap();
+    fn new(node: &str, re: &Regex) -> Result<Self, InputError> {
+        let caps = re.captures(node).ok_or_else(|| {
+            InputError::expected("a node like `/dev/grid/node-x0-y0 10T 8T 2T 80%`", node)
+        })?;
 
-        let x = i32::from_str(caps.name("x").unwrap().as_str()).unwrap();
-        let y = i32::from_str(caps.name("y").unwrap().as_str()).unwrap();
-        let used = u32::from_str(caps.name("used").unwrap().as_str()).unwrap();
-        let avail = u32::from_str(caps.name("avail").unwrap().as_str()).unwrap();
+        let x = solutions::number(caps.name("x").unwrap().as_str(), "a coordinate")?;
+        let y = solutions::number(caps.name("y").unwrap().as_str(), "a coordinate")?;
+        let used = solutions::number(caps.name("used").unwrap().as_str(), "a size")?;
+        let avail = solutions::number(caps.name("avail").unwrap().as_str(), "a size")?;
 
-        Self {
+        Ok(Self {
             coord: Coordinate { x, y },
             used,
             avail,
-        }
+        })
     }
 }
 
@@ -128,10 +138,33 @@ struct Coordinate {
     y: i32,
 }
 
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Options {
+    /// Whether the input is the small sample grid.
+    pub test_input: bool,
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input<'a> = Grid;
+    type Options = Options;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        Grid::new(&solutions::lines(input)?)
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(input).to_string()
+    }
+
+    fn part_2(input: &Self::Input<'_>, options: &Self::Options) -> Option<String> {
+        Some(solve_2(input, options.test_input).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
 
     #[test]
@@ -141,37 +174,34 @@ mod tests {
 
     #[test]
     fn day_22_part_01_solution() {
-        let input = include_str!("../../inputs/day_22.txt")
-            .lines()
-            .collect_vec();
+        let input = Day22::parse(include_str!("../../../inputs/2016/day_22.txt")).unwrap();
 
         assert_eq!(976, solve_1(&input));
     }
 
     #[test]
     fn day_22_part_02_sample() {
-        let sample = vec![
-            "root@ebhq-gridcenter# df -h",
-            "Filesystem            Size  Used  Avail  Use%",
-            "/dev/grid/node-x0-y0   10T    8T     2T   80%",
-            "/dev/grid/node-x0-y1   11T    6T     5T   54%",
-            "/dev/grid/node-x0-y2   32T   28T     4T   87%",
-            "/dev/grid/node-x1-y0    9T    7T     2T   77%",
-            "/dev/grid/node-x1-y1    8T    0T     8T    0%",
-            "/dev/grid/node-x1-y2   11T    7T     4T   63%",
-            "/dev/grid/node-x2-y0   10T    6T     4T   60%",
-            "/dev/grid/node-x2-y1    9T    8T     1T   88%",
-            "/dev/grid/node-x2-y2    9T    6T     3T   66%",
-        ];
+        let sample = Day22::parse(
+            "root@ebhq-gridcenter# df -h\n\
+             Filesystem            Size  Used  Avail  Use%\n\
+             /dev/grid/node-x0-y0   10T    8T     2T   80%\n\
+             /dev/grid/node-x0-y1   11T    6T     5T   54%\n\
+             /dev/grid/node-x0-y2   32T   28T     4T   87%\n\
+             /dev/grid/node-x1-y0    9T    7T     2T   77%\n\
+             /dev/grid/node-x1-y1    8T    0T     8T    0%\n\
+             /dev/grid/node-x1-y2   11T    7T     4T   63%\n\
+             /dev/grid/node-x2-y0   10T    6T     4T   60%\n\
+             /dev/grid/node-x2-y1    9T    8T     1T   88%\n\
+             /dev/grid/node-x2-y2    9T    6T     3T   66%",
+        )
+        .unwrap();
 
         assert_eq!(7, solve_2(&sample, true));
     }
 
     #[test]
     fn day_22_part_02_solution() {
-        let input = include_str!("../../inputs/day_22.txt")
-            .lines()
-            .collect_vec();
+        let input = Day22::parse(include_str!("../../../inputs/2016/day_22.txt")).unwrap();
 
         assert_eq!(209, solve_2(&input, false));
     }
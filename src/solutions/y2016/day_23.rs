@@ -0,0 +1,79 @@
+use crate::computer::{Computer, Register};
+use crate::solutions::{self, InputError, Solution};
+
+pub fn solve_1(code: &[&str]) -> i64 {
+    Computer::new(code, vec![(Register::A, 7)])
+        .unwrap()
+        .run(true, 0)
+        .unwrap()
+        .registers[&Register::A]
+}
+
+pub fn solve_2(code: &[&str]) -> i64 {
+    Computer::new(code, vec![(Register::A, 12)])
+        .unwrap()
+        .run(true, 0)
+        .unwrap()
+        .registers[&Register::A]
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input<'a> = Vec<&'a str>;
+    type Options = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        let code = solutions::lines(input)?;
+        Computer::new(&code, vec![]).map_err(|e| InputError::Invalid(e.to_string()))?;
+
+        Ok(code)
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(input).to_string()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        Some(solve_2(input).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn day_23_part_01_sample() {
+        let sample = vec![
+            "cpy 2 a", "tgl a", "tgl a", "tgl a", "cpy 1 a", "dec a", "dec a",
+        ];
+
+        assert_eq!(3, solve_1(&sample));
+    }
+
+    #[test]
+    fn day_23_part_01_solution() {
+        let input = include_str!("../../../inputs/2016/day_23.txt")
+            .lines()
+            .collect_vec();
+
+        assert_eq!(11_893, solve_1(&input));
+    }
+
+    #[test]
+    fn day_23_part_02_sample() {
+        // No sample inputs for part 2
+    }
+
+    #[test]
+    fn day_23_part_02_solution() {
+        let input = include_str!("../../../inputs/2016/day_23.txt")
+            .lines()
+            .collect_vec();
+
+        assert_eq!(479_008_453, solve_2(&input));
+    }
+}
@@ -1,46 +1,53 @@
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::Not;
 
-pub fn solve_1(blueprint: &[&str]) -> u32 {
-    Ducts::new(blueprint).total_shortest_path(false)
+use crate::solutions::{self, InputError, Solution};
+
+pub fn solve_1(ducts: &Ducts) -> u32 {
+    ducts.total_shortest_path(false)
 }
 
-pub fn solve_2(blueprint: &[&str]) -> u32 {
-    Ducts::new(blueprint).total_shortest_path(true)
+pub fn solve_2(ducts: &Ducts) -> u32 {
+    ducts.total_shortest_path(true)
 }
 
 #[derive(Debug)]
-struct Ducts {
+pub struct Ducts {
     locations: Vec<Coordinate>,
     neighbours: HashMap<Coordinate, Vec<Coordinate>>,
 }
 
 impl Ducts {
-    fn new(blueprint: &[&str]) -> Self {
-        let blueprint = blueprint.iter().map(|s| s.as_bytes()).collect_vec();
+    /// The ducts of the blueprint, in which every location can be reached from location `0`.
+    fn new(blueprint: &[&str]) -> Result<Self, InputError> {
         let mut passages: HashSet<Coordinate> = HashSet::default();
         let mut locations: HashMap<Coordinate, u8> = HashMap::default();
 
-        for y in 0..blueprint.len() {
-            for x in 0..blueprint[0].len() {
+        for (y, row) in blueprint.iter().enumerate() {
+            for (x, tile) in row.bytes().enumerate() {
                 let c = Coordinate {
                     x: x as i32,
                     y: y as i32,
                 };
-                match blueprint[y][x] {
+                match tile {
                     b'0' | b'1' | b'2' | b'3' | b'4' | b'5' | b'6' | b'7' | b'8' | b'9' => {
                         passages.insert(c);
-                        locations.insert(c, blueprint[y][x]);
+                        locations.insert(c, tile);
                     }
                     b'.' => {
                         passages.insert(c);
                     }
                     b'#' => {}
-                    _ => unreachable!(),
+                    _ => return Err(InputError::expected("`#`, `.` or a digit", row)),
                 }
             }
         }
 
+        if locations.values().any(|&l| l == b'0').not() {
+            return Err(InputError::Invalid("expected location 0".to_string()));
+        }
+
         let locations = locations
             .into_iter()
             .sorted_by_key(|(_, v)| *v)
@@ -59,9 +66,33 @@ impl Ducts {
             })
             .collect();
 
-        Self {
+        let ducts = Self {
             locations,
             neighbours,
+        };
+        ducts.check_locations()?;
+
+        Ok(ducts)
+    }
+
+    fn check_locations(&self) -> Result<(), InputError> {
+        let start = &self.locations[0];
+        let mut reached = HashSet::from([start]);
+        let mut to_visit = vec![start];
+        while let Some(c) = to_visit.pop() {
+            for n in &self.neighbours[c] {
+                if reached.insert(n) {
+                    to_visit.push(n);
+                }
+            }
+        }
+
+        match self.locations.iter().find(|l| reached.contains(l).not()) {
+            Some(l) => Err(InputError::Invalid(format!(
+                "location at ({}, {}) can't be reached from location 0",
+                l.x, l.y
+            ))),
+            None => Ok(()),
         }
     }
 
@@ -143,30 +174,46 @@ impl Coordinate {
     }
 }
 
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input<'a> = Ducts;
+    type Options = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        Ducts::new(&solutions::lines(input)?)
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(input).to_string()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        Some(solve_2(input).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
 
     #[test]
     fn day_24_part_01_sample() {
-        let sample = vec![
-            "###########",
-            "#0.1.....2#",
-            "#.#######.#",
-            "#4.......3#",
-            "###########",
-        ];
+        let sample = Day24::parse(
+            "###########\n\
+             #0.1.....2#\n\
+             #.#######.#\n\
+             #4.......3#\n\
+             ###########",
+        )
+        .unwrap();
 
         assert_eq!(14, solve_1(&sample));
     }
 
     #[test]
     fn day_24_part_01_solution() {
-        let input = include_str!("../../inputs/day_24.txt")
-            .lines()
-            .collect_vec();
+        let input = Day24::parse(include_str!("../../../inputs/2016/day_24.txt")).unwrap();
 
         assert_eq!(474, solve_1(&input));
     }
@@ -178,10 +225,20 @@ mod tests {
 
     #[test]
     fn day_24_part_02_solution() {
-        let input = include_str!("../../inputs/day_24.txt")
-            .lines()
-            .collect_vec();
+        let input = Day24::parse(include_str!("../../../inputs/2016/day_24.txt")).unwrap();
 
         assert_eq!(696, solve_2(&input));
     }
+
+    #[test]
+    fn day_24_rejects_invalid_blueprints() {
+        assert_eq!(
+            "expected location 0",
+            Day24::parse("#####\n#1.2#\n#####").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "location at (3, 1) can't be reached from location 0",
+            Day24::parse("#####\n#0#1#\n#####").unwrap_err().to_string()
+        );
+    }
 }
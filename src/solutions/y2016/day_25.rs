@@ -0,0 +1,65 @@
+use crate::computer::{ClockSignal, Computer, Register};
+use crate::solutions::{self, InputError, Solution};
+
+pub fn solve_1(code: &[&str]) -> i32 {
+    (0..i32::MAX)
+        .find(|signal| {
+            Computer::new(code, vec![(Register::A, i64::from(*signal))])
+                .unwrap()
+                .check_clock_signal(true, 1_000)
+                == ClockSignal::Proven
+        })
+        .unwrap()
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input<'a> = Vec<&'a str>;
+    type Options = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, InputError> {
+        let code = solutions::lines(input)?;
+        Computer::new(&code, vec![]).map_err(|e| InputError::Invalid(e.to_string()))?;
+
+        Ok(code)
+    }
+
+    fn part_1(input: &Self::Input<'_>, _options: &Self::Options) -> String {
+        solve_1(input).to_string()
+    }
+
+    /// There is no part 2: you activate all fifty stars and transmit the signal. The star atop the
+    /// antenna begins to glow.
+    fn part_2(_input: &Self::Input<'_>, _options: &Self::Options) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn day_25_part_01_sample() {
+        // No sample inputs for part 1
+    }
+
+    #[test]
+    fn day_25_part_01_solution() {
+        let input = include_str!("../../../inputs/2016/day_25.txt")
+            .lines()
+            .collect_vec();
+
+        assert_eq!(175, solve_1(&input));
+    }
+
+    #[test]
+    fn day_25_part_02_sample() {
+        let sample = Day25::parse("out a").unwrap();
+
+        assert_eq!(None, Day25::part_2(&sample, &()));
+    }
+}